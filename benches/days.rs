@@ -0,0 +1,219 @@
+//! Benchmarks for parsing and both parts of every registered day.
+//!
+//! ```text
+//! cargo bench                              # run everything, compare with (then replace) `base`
+//! cargo bench -- 15                        # only day 15
+//! cargo bench -- --save-baseline before    # store results as `before`
+//! cargo bench -- --baseline before         # compare with `before`, don't store anything
+//! ```
+//!
+//! Baselines are stored as plain text in `target/aoc-bench/<name>.txt`, one line per
+//! `<day> <parse|1|2> <median ns>`.
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+use aoc2021::input::InputDir;
+use aoc2021::solution::{registry, RunConfig};
+
+const USAGE: &str = "\
+Usage: cargo bench [-- [DAY...] [--baseline NAME | --save-baseline NAME]]
+
+Options:
+  --baseline NAME        Compare with the stored baseline NAME, without overwriting it
+  --save-baseline NAME   Compare with, then overwrite, the baseline NAME (default: base)
+  --measurement-time S   Seconds spent measuring each day (default: 2)";
+
+/// Regressions/improvements smaller than this are reported as noise.
+const NOISE_THRESHOLD: f64 = 0.05;
+const MIN_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 100;
+
+/// Median times, keyed by (day, "parse" | "1" | "2").
+type Baseline = HashMap<(u8, String), Duration>;
+
+struct Options {
+    days: Vec<u8>,
+    baseline: String,
+    save: bool,
+    measurement_time: Duration,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        days: vec![],
+        baseline: "base".to_string(),
+        save: true,
+        measurement_time: Duration::from_secs(2),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{}'", arg));
+        match arg.as_str() {
+            // passed by `cargo bench`
+            "--bench" => {}
+            "--baseline" => {
+                options.baseline = value()?;
+                options.save = false;
+            }
+            "--save-baseline" => {
+                options.baseline = value()?;
+                options.save = true;
+            }
+            "--measurement-time" => {
+                let secs: f64 = value()?
+                    .parse()
+                    .map_err(|e| format!("invalid measurement time: {}", e))?;
+                options.measurement_time = Duration::from_secs_f64(secs);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            day => options.days.push(
+                day.parse()
+                    .map_err(|_| format!("unexpected argument '{}'", day))?,
+            ),
+        }
+    }
+    Ok(options)
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    let target = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target"));
+    target.join("aoc-bench").join(format!("{}.txt", name))
+}
+
+fn load_baseline(name: &str) -> Baseline {
+    let contents = fs::read_to_string(baseline_path(name)).unwrap_or_default();
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let day = fields.next()?.parse().ok()?;
+            let step = fields.next()?.to_string();
+            let nanos = fields.next()?.parse().ok()?;
+            Some(((day, step), Duration::from_nanos(nanos)))
+        })
+        .collect()
+}
+
+fn save_baseline(name: &str, baseline: &Baseline) -> std::io::Result<()> {
+    let path = baseline_path(name);
+    fs::create_dir_all(path.parent().unwrap())?;
+    let mut entries: Vec<_> = baseline.iter().collect();
+    entries.sort();
+    let contents: String = entries
+        .into_iter()
+        .map(|((day, step), median)| format!("{} {} {}\n", day, step, median.as_nanos()))
+        .collect();
+    fs::write(path, contents)
+}
+
+fn median(samples: &mut [Duration]) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+/// Relative change against the baseline, e.g. `-12.3% (improved)`.
+fn change(old: Option<&Duration>, new: Duration) -> String {
+    let Some(old) = old else {
+        return String::new();
+    };
+    let ratio = new.as_secs_f64() / old.as_secs_f64() - 1.0;
+    let verdict = if ratio.abs() < NOISE_THRESHOLD {
+        "no change"
+    } else if ratio < 0.0 {
+        "improved"
+    } else {
+        "REGRESSED"
+    };
+    format!("{:+7.1}% ({})", ratio * 100.0, verdict)
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|e| {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        process::exit(2);
+    });
+    let dir = InputDir::resolve(None);
+    let previous = load_baseline(&options.baseline);
+    let mut current = Baseline::new();
+
+    println!(
+        "{:>3}  {:>5}  {:>10}  {:>10}  {:>10}  change vs '{}'",
+        "Day", "Step", "median", "min", "max", options.baseline
+    );
+    for runner in registry() {
+        let day = runner.day();
+        if !options.days.is_empty() && !options.days.contains(&day) {
+            continue;
+        }
+        let input = match runner.input(&dir, false) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{:>3}  skipped: {}", day, e);
+                continue;
+            }
+        };
+
+        // One warm-up run, which also tells us roughly how long a run takes
+        let start = Instant::now();
+        let config = RunConfig::default();
+        if let Err(e) = runner.solve(&input, None, &config) {
+            println!("{:>3}  skipped: {}", day, e);
+            continue;
+        }
+        let once = start.elapsed().max(Duration::from_nanos(1));
+        let samples = (options.measurement_time.as_nanos() / once.as_nanos()) as usize;
+        let samples = samples.clamp(MIN_SAMPLES, MAX_SAMPLES);
+
+        let mut steps: Vec<(String, Vec<Duration>)> = vec![];
+        for _ in 0..samples {
+            let solved = runner
+                .solve(&input, None, &config)
+                .expect("solved during warm-up");
+            let timings = std::iter::once(("parse".to_string(), solved.parse)).chain(
+                solved
+                    .parts
+                    .iter()
+                    .map(|part| (part.part.to_string(), part.elapsed)),
+            );
+            for (step, elapsed) in timings {
+                match steps.iter_mut().find(|(s, _)| *s == step) {
+                    Some((_, times)) => times.push(elapsed),
+                    None => steps.push((step, vec![elapsed])),
+                }
+            }
+        }
+
+        for (step, mut times) in steps {
+            let median = median(&mut times);
+            let key = (day, step);
+            println!(
+                "{:>3}  {:>5}  {:>10.2?}  {:>10.2?}  {:>10.2?}  {}",
+                day,
+                key.1,
+                median,
+                times[0],
+                times[times.len() - 1],
+                change(previous.get(&key), median)
+            );
+            current.insert(key, median);
+        }
+    }
+
+    if options.save {
+        // keep entries for days that weren't run this time
+        let mut merged = previous;
+        merged.extend(current);
+        if let Err(e) = save_baseline(&options.baseline, &merged) {
+            eprintln!("error: can't save baseline '{}': {}", options.baseline, e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Terminal animations for the puzzles that are fun to watch (day11's octopuses, day13's folds,
+//! day15's search).
+//!
+//! A day implements [`Scene`] for whatever it wants to show, then calls [`Animation::draw`]
+//! whenever there's something new to see. The animation takes care of the terminal: it draws on
+//! the alternate screen (so the animation doesn't bury earlier output), paces frames to the
+//! configured FPS, and restores the screen afterwards, printing the final frame.
+//!
+//! While an animation is playing, typing `p` + Enter pauses it; Enter on its own then advances a
+//! single frame, and `p` + Enter resumes. `q` + Enter stops drawing, letting the puzzle finish at
+//! full speed. Controls are read by a single thread for the whole process, so they reach whichever
+//! animation is playing (e.g. each day's in turn when running them all).
+//!
+//! A headless animation renders frames without writing or waiting, which keeps tests and
+//! benchmarks fast while still exercising the drawing code.
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+/// Clear whatever a previous, longer frame left below this one.
+const CLEAR_BELOW: &str = "\x1b[J";
+
+/// Something that can be drawn as frames of an animation.
+pub trait Scene {
+    /// Text of the current frame, drawn from the top left corner of the screen.
+    ///
+    /// `redraw` is set when the screen was cleared, so everything has to be drawn. Otherwise the
+    /// previous frame is still on screen, and scenes may draw only what changed (positioning the
+    /// cursor with escape codes as they go).
+    fn render(&mut self, redraw: bool) -> String;
+
+    /// The current frame as plain lines of text, printed where the cursor is when the animation
+    /// finishes. Scenes that position the cursor in `render` need to override this.
+    fn last_frame(&mut self) -> String {
+        self.render(true)
+    }
+}
+
+/// A frame of plain text, e.g. something's `Display` output.
+pub struct Text(pub String);
+
+impl Scene for Text {
+    fn render(&mut self, _redraw: bool) -> String {
+        self.0.clone()
+    }
+}
+
+/// Keyboard controls, typed as lines on stdin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    /// `p`
+    Pause,
+    /// empty line
+    Step,
+    /// `q`
+    Quit,
+}
+
+/// Controls typed on stdin, shared by every animation. `None` when stdin isn't a terminal (it may
+/// hold the puzzle input), so there is nothing to read controls from.
+fn controls() -> Option<&'static Mutex<Receiver<Control>>> {
+    static CONTROLS: OnceLock<Option<Mutex<Receiver<Control>>>> = OnceLock::new();
+    CONTROLS
+        .get_or_init(|| {
+            if !io::stdin().is_terminal() {
+                return None;
+            }
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                for line in io::stdin().lock().lines() {
+                    let control = match line.as_deref().map(str::trim) {
+                        Ok("p") => Control::Pause,
+                        Ok("q") => Control::Quit,
+                        Ok(_) => Control::Step,
+                        Err(_) => break,
+                    };
+                    if sender.send(control).is_err() {
+                        break;
+                    }
+                }
+            });
+            Some(Mutex::new(receiver))
+        })
+        .as_ref()
+}
+
+/// How an [`Animation`] plays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Playback {
+    pub fps: f64,
+    /// Wait for Enter before each frame, as if paused from the start.
+    pub step: bool,
+    /// Render frames without drawing them.
+    pub headless: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Playback {
+            fps: 30.0,
+            step: false,
+            headless: false,
+        }
+    }
+}
+
+pub struct Animation {
+    playback: Playback,
+    paused: bool,
+    /// Whether we're on the alternate screen, i.e. have drawn a frame and haven't finished.
+    started: bool,
+    last_frame: Option<Instant>,
+    controls: Option<&'static Mutex<Receiver<Control>>>,
+    frames: usize,
+}
+
+impl Animation {
+    pub fn new(playback: Playback) -> Self {
+        Animation {
+            playback,
+            paused: playback.step,
+            started: false,
+            last_frame: None,
+            controls: None,
+            frames: 0,
+        }
+    }
+
+    /// An animation that only renders its frames.
+    pub fn headless() -> Self {
+        Animation::new(Playback {
+            headless: true,
+            ..Default::default()
+        })
+    }
+
+    /// Number of frames rendered so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    fn write(text: &str) {
+        let mut stdout = io::stdout().lock();
+        let _ = stdout.write_all(text.as_bytes());
+        let _ = stdout.flush();
+    }
+
+    /// Draw the next frame of `scene`, then wait until it's time for the one after.
+    pub fn draw<S: Scene + ?Sized>(&mut self, scene: &mut S) {
+        self.show(scene, self.frame_time());
+    }
+
+    /// Draw the next frame of `scene`, keeping it on screen for `duration` instead of one frame
+    /// (e.g. to linger on the end of a step).
+    pub fn hold<S: Scene + ?Sized>(&mut self, scene: &mut S, duration: Duration) {
+        self.show(scene, duration);
+    }
+
+    fn frame_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.playback.fps.max(0.001))
+    }
+
+    fn show<S: Scene + ?Sized>(&mut self, scene: &mut S, duration: Duration) {
+        self.frames += 1;
+        if self.playback.headless {
+            scene.render(self.frames == 1);
+            return;
+        }
+        let mut frame = String::new();
+        let redraw = !self.started;
+        if redraw {
+            self.start();
+            frame.push_str(CLEAR_SCREEN);
+        }
+        frame.push_str(CURSOR_HOME);
+        frame.push_str(&scene.render(redraw));
+        frame.push_str(CLEAR_BELOW);
+        Animation::write(&frame);
+        self.wait(duration);
+    }
+
+    fn start(&mut self) {
+        self.started = true;
+        Animation::write(ENTER_ALTERNATE_SCREEN);
+        self.controls = controls();
+        // forget anything typed while no animation was playing (e.g. a `q` for the previous one)
+        if let Some(controls) = self.controls {
+            let controls = controls.lock().unwrap_or_else(PoisonError::into_inner);
+            while controls.try_recv().is_ok() {}
+        }
+    }
+
+    /// Wait until `duration` after the previous frame, or for Enter while paused.
+    fn wait(&mut self, duration: Duration) {
+        let deadline = self.last_frame.unwrap_or_else(Instant::now) + duration;
+        loop {
+            let controls = self
+                .controls
+                .map(|controls| controls.lock().unwrap_or_else(PoisonError::into_inner));
+            let control = match &controls {
+                Some(controls) if self.paused => controls.recv().ok(),
+                Some(controls) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    match controls.recv_timeout(timeout) {
+                        Ok(control) => Some(control),
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => None,
+                    }
+                }
+                None => None,
+            };
+            drop(controls);
+            match control {
+                Some(Control::Pause) => self.paused = !self.paused,
+                Some(Control::Step) if self.paused => break,
+                Some(Control::Step) => {}
+                Some(Control::Quit) => {
+                    self.stop();
+                    self.playback.headless = true;
+                    break;
+                }
+                // no (more) controls: just keep time
+                None => {
+                    self.controls = None;
+                    self.paused = false;
+                    thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                    break;
+                }
+            }
+        }
+        self.last_frame = Some(Instant::now());
+    }
+
+    fn stop(&mut self) {
+        if self.started {
+            self.started = false;
+            Animation::write(LEAVE_ALTERNATE_SCREEN);
+        }
+    }
+
+    /// Leave the alternate screen, printing the final frame of `scene` where it stays visible.
+    pub fn finish<S: Scene + ?Sized>(&mut self, scene: &mut S) {
+        if self.started {
+            self.stop();
+            Animation::write(&scene.last_frame());
+        }
+    }
+}
+
+/// Restore the terminal even if the animation didn't get to finish (e.g. an error or panic).
+impl Drop for Animation {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts its frames, and whether each one was a redraw.
+    struct Counter(Vec<bool>);
+
+    impl Scene for Counter {
+        fn render(&mut self, redraw: bool) -> String {
+            self.0.push(redraw);
+            format!("frame {}\n", self.0.len())
+        }
+    }
+
+    #[test]
+    fn test_headless() {
+        let mut counter = Counter(vec![]);
+        let mut animation = Animation::headless();
+        let start = Instant::now();
+        animation.draw(&mut counter);
+        animation.hold(&mut counter, Duration::from_secs(10));
+        animation.draw(&mut counter);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(animation.frames(), 3);
+        assert_eq!(counter.0, [true, false, false]);
+        animation.finish(&mut counter);
+        assert_eq!(counter.0.len(), 3);
+    }
+}
@@ -0,0 +1,479 @@
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+use aoc2021::solution::{RunConfig, Verbosity};
+
+pub const USAGE: &str = "\
+Usage: aoc2021 run <DAYS> [OPTIONS]
+       aoc2021 <DAYS> [OPTIONS]
+       aoc2021 help
+       cat my_input.txt | aoc2021 run <DAY> -
+
+<DAYS> is a single day (`7`), a range (`1..=10`, `1..11`) or `all`.
+Running more than one day prints a summary table.
+
+Options:
+  -p, --part <1|2>     Only solve the given part (default: both)
+  -i, --input <PATH>   Read puzzle input from PATH instead of dayNN.txt
+  -,  --stdin          Read puzzle input from stdin (same as `--input -`)
+  -e, --example        Use the example input (dayNN_example.txt)
+  -t, --time           Report time spent parsing and solving each part
+      --format <FORMAT>
+                       Output format: text (default) or json, one
+                       {day, part, answer, elapsed_ms} record per part
+      --input-dir <DIR>
+                       Directory holding dayNN.txt files (default: $AOC_INPUT_DIR,
+                       or the crate's inputs/ directory)
+      --animate        Animate the puzzle in the terminal (day11, day13, day15)
+      --fps <N>        Draw N animation frames per second (default: the day's own)
+      --step           Start the animation paused: Enter draws the next frame
+      --headless       Render animation frames without drawing them
+      --frame-skip <N> Skip N search steps between animation frames (day15;
+                       default: whatever draws the search in 200 frames)
+      --debug          Print debugging output (day12)
+      --steps <N>      Run day14's pair insertion N times instead of solving
+                       both parts
+      --heatmap <FILE> Write day15's search as a PPM image to FILE (the part 2
+                       map, unless `--part 1` is given)
+  -h, --help           Print this message";
+
+/// Bad command line -- printed together with `USAGE`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UsageError(String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for UsageError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Help,
+}
+
+/// Which days to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Days {
+    One(u8),
+    /// Inclusive range of days; days that aren't implemented are skipped.
+    Range(u8, u8),
+}
+
+impl Default for Days {
+    fn default() -> Self {
+        Days::Range(1, 25)
+    }
+}
+
+/// How answers are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RunArgs {
+    pub days: Days,
+    pub part: Option<u8>,
+    pub input: Option<PathBuf>,
+    /// Read the puzzle input from stdin.
+    pub stdin: bool,
+    pub input_dir: Option<PathBuf>,
+    pub example: bool,
+    /// Report parse/part durations.
+    pub time: bool,
+    pub format: Format,
+    pub animate: bool,
+    pub fps: Option<u32>,
+    pub step: bool,
+    pub headless: bool,
+    pub frame_skip: Option<usize>,
+    pub debug: bool,
+    /// Number of day14 pair insertion steps to run instead of the puzzle's parts.
+    pub steps: Option<usize>,
+    /// Where to write day15's heatmap.
+    pub heatmap: Option<PathBuf>,
+}
+
+impl RunArgs {
+    /// Options that are passed on to the solvers.
+    pub fn config(&self) -> RunConfig {
+        RunConfig {
+            verbosity: if self.debug {
+                Verbosity::Debug
+            } else {
+                Verbosity::Normal
+            },
+            animate: self.animate,
+            fps: self.fps,
+            step: self.step,
+            headless: self.headless,
+            frame_skip: self.frame_skip,
+            example: self.example,
+            timing: self.time,
+        }
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u8, UsageError> {
+    match arg.parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(UsageError(format!(
+            "invalid day '{}': expected an integer between 1-25",
+            arg
+        ))),
+    }
+}
+
+/// Parse `all`, a range (`a..=b` or `a..b`) or a single day.
+fn parse_days(arg: &str) -> Result<Days, UsageError> {
+    if arg == "all" {
+        return Ok(Days::default());
+    }
+    let (first, last) = if let Some((first, last)) = arg.split_once("..=") {
+        (parse_day(first)?, parse_day(last)?)
+    } else if let Some((first, end)) = arg.split_once("..") {
+        // the end is exclusive, so `1..26` runs every day
+        let last = match end.parse::<u8>() {
+            Ok(end @ 2..=26) => end - 1,
+            _ => {
+                return Err(UsageError(format!(
+                    "invalid end of range '{}': expected an integer between 2-26",
+                    end
+                )))
+            }
+        };
+        (parse_day(first)?, last)
+    } else {
+        return Ok(Days::One(parse_day(arg)?));
+    };
+    if first > last {
+        return Err(UsageError(format!("empty range of days '{}'", arg)));
+    }
+    Ok(Days::Range(first, last))
+}
+
+fn parse_format(arg: &str) -> Result<Format, UsageError> {
+    match arg {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(UsageError(format!(
+            "invalid format '{}': expected text or json",
+            arg
+        ))),
+    }
+}
+
+fn parse_fps(arg: &str) -> Result<u32, UsageError> {
+    match arg.parse() {
+        Ok(fps) if fps > 0 => Ok(fps),
+        _ => Err(UsageError(format!(
+            "invalid frame rate '{}': expected a positive integer",
+            arg
+        ))),
+    }
+}
+
+/// Parse the value of a count option (`--steps`, `--frame-skip`), called `what` in errors.
+fn parse_count(arg: &str, what: &str) -> Result<usize, UsageError> {
+    arg.parse().map_err(|_| {
+        UsageError(format!(
+            "invalid {} '{}': expected a non-negative integer",
+            what, arg
+        ))
+    })
+}
+
+fn parse_part(arg: &str) -> Result<u8, UsageError> {
+    match arg {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(UsageError(format!(
+            "invalid part '{}': expected 1 or 2",
+            arg
+        ))),
+    }
+}
+
+/// Parse everything after the subcommand/day, i.e. `[OPTIONS]`.
+fn parse_run_options<I: Iterator<Item = String>>(
+    days: Days,
+    mut args: I,
+) -> Result<RunArgs, UsageError> {
+    let mut run = RunArgs {
+        days,
+        ..Default::default()
+    };
+    while let Some(arg) = args.next() {
+        // accept both `--part 2` and `--part=2`
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        if inline_value.is_some()
+            && !matches!(
+                flag.as_str(),
+                "--part"
+                    | "--input"
+                    | "--input-dir"
+                    | "--format"
+                    | "--fps"
+                    | "--frame-skip"
+                    | "--steps"
+                    | "--heatmap"
+            )
+        {
+            return Err(UsageError(format!("'{}' does not take a value", flag)));
+        }
+        let mut value = |name: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| UsageError(format!("missing value for '{}'", name)))
+        };
+        match flag.as_str() {
+            "-p" | "--part" => run.part = Some(parse_part(&value(&flag)?)?),
+            "-i" | "--input" => match value(&flag)?.as_str() {
+                "-" => run.stdin = true,
+                path => run.input = Some(PathBuf::from(path)),
+            },
+            "-" | "--stdin" => run.stdin = true,
+            "--input-dir" => run.input_dir = Some(PathBuf::from(value(&flag)?)),
+            "-e" | "--example" => run.example = true,
+            "-t" | "--time" => run.time = true,
+            "--format" => run.format = parse_format(&value(&flag)?)?,
+            "--animate" => run.animate = true,
+            "--fps" => run.fps = Some(parse_fps(&value(&flag)?)?),
+            "--step" => run.step = true,
+            "--headless" => run.headless = true,
+            "--debug" => run.debug = true,
+            "--frame-skip" => run.frame_skip = Some(parse_count(&value(&flag)?, "frame skip")?),
+            "--steps" => run.steps = Some(parse_count(&value(&flag)?, "number of steps")?),
+            "--heatmap" => run.heatmap = Some(PathBuf::from(value(&flag)?)),
+            _ => return Err(UsageError(format!("unexpected argument '{}'", arg))),
+        }
+    }
+    if (run.input.is_some() || run.stdin) && matches!(run.days, Days::Range(..)) {
+        return Err(UsageError(
+            "'--input' and '--stdin' can only be used when running a single day".to_string(),
+        ));
+    }
+    if run.input.is_some() && run.stdin {
+        return Err(UsageError(
+            "'--input' and '--stdin' cannot be used together".to_string(),
+        ));
+    }
+    if run.example && (run.input.is_some() || run.stdin) {
+        return Err(UsageError(
+            "'--example' cannot be used with '--input' or '--stdin'".to_string(),
+        ));
+    }
+    if (run.fps.is_some() || run.step || run.headless || run.frame_skip.is_some()) && !run.animate {
+        return Err(UsageError(
+            "'--fps', '--step', '--headless' and '--frame-skip' can only be used with '--animate'"
+                .to_string(),
+        ));
+    }
+    if run.steps.is_some() && run.days != Days::One(14) {
+        return Err(UsageError(
+            "'--steps' can only be used when running day 14".to_string(),
+        ));
+    }
+    if run.steps.is_some() && (run.part.is_some() || run.format == Format::Json) {
+        return Err(UsageError(
+            "'--steps' cannot be used with '--part' or '--format json'".to_string(),
+        ));
+    }
+    // both print on stdout, which has to hold nothing but the JSON
+    if (run.debug || run.animate) && run.format == Format::Json {
+        return Err(UsageError(
+            "'--debug' and '--animate' cannot be used with '--format json'".to_string(),
+        ));
+    }
+    if run.heatmap.is_some() && run.days != Days::One(15) {
+        return Err(UsageError(
+            "'--heatmap' can only be used when running day 15".to_string(),
+        ));
+    }
+    Ok(run)
+}
+
+/// Parse command line arguments (without the program name).
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, UsageError> {
+    let mut args = args.into_iter();
+    let first = args
+        .next()
+        .ok_or_else(|| UsageError("missing command".to_string()))?;
+    match first.as_str() {
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        "run" => {
+            let days = args
+                .next()
+                .ok_or_else(|| UsageError("missing day for 'run'".to_string()))?;
+            Ok(Command::Run(parse_run_options(parse_days(&days)?, args)?))
+        }
+        // `aoc2021 <DAYS>` is shorthand for `aoc2021 run <DAYS>`
+        days if days == "all" || days.starts_with(|c: char| c.is_ascii_digit()) => {
+            Ok(Command::Run(parse_run_options(parse_days(days)?, args)?))
+        }
+        _ => Err(UsageError(format!("unknown command '{}'", first))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Command, UsageError> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_run_with_options() {
+        let expected = RunArgs {
+            days: Days::One(5),
+            part: Some(2),
+            example: true,
+            time: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse("run 5 --part 2 --example --time"),
+            Ok(Command::Run(expected))
+        );
+        assert_eq!(
+            parse("5 -p=2 -e").map_err(|e| e.to_string()),
+            Err("unexpected argument '-p=2'".to_string())
+        );
+        assert_eq!(
+            parse("all --input-dir=/aoc/inputs -e"),
+            Ok(Command::Run(RunArgs {
+                input_dir: Some(PathBuf::from("/aoc/inputs")),
+                example: true,
+                ..Default::default()
+            }))
+        );
+        assert_eq!(
+            parse("1..=3 --format=json"),
+            Ok(Command::Run(RunArgs {
+                days: Days::Range(1, 3),
+                format: Format::Json,
+                ..Default::default()
+            }))
+        );
+        assert_eq!(
+            parse("15 --input=my.txt --debug --heatmap=out.ppm"),
+            Ok(Command::Run(RunArgs {
+                days: Days::One(15),
+                input: Some(PathBuf::from("my.txt")),
+                debug: true,
+                heatmap: Some(PathBuf::from("out.ppm")),
+                ..Default::default()
+            }))
+        );
+        assert_eq!(
+            parse("14 -e --steps=20"),
+            Ok(Command::Run(RunArgs {
+                days: Days::One(14),
+                example: true,
+                steps: Some(20),
+                ..Default::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_config() {
+        let config = |line| match parse(line) {
+            Ok(Command::Run(args)) => args.config(),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(config("11"), RunConfig::default());
+        assert_eq!(
+            config("15 -e --time --animate --debug"),
+            RunConfig {
+                verbosity: Verbosity::Debug,
+                animate: true,
+                example: true,
+                timing: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(config("15 --animate --frame-skip=0").frame_skip, Some(0));
+        let config = config("11 --animate --fps=60 --step --headless");
+        assert_eq!(
+            (config.fps, config.step, config.headless),
+            (Some(60), true, true)
+        );
+        assert_eq!(config.playback(30.0).fps, 60.0);
+        assert_eq!(RunConfig::default().playback(30.0).fps, 30.0);
+    }
+
+    #[test]
+    fn test_stdin() {
+        let expected = Ok(Command::Run(RunArgs {
+            days: Days::One(3),
+            stdin: true,
+            ..Default::default()
+        }));
+        assert_eq!(parse("run 3 -"), expected);
+        assert_eq!(parse("run 3 --stdin"), expected);
+        assert_eq!(parse("3 --input -"), expected);
+        assert_eq!(
+            parse("3 -i=-"),
+            Err(UsageError("unexpected argument '-i=-'".into()))
+        );
+        assert!(parse("all -").is_err());
+        assert!(parse("3 - --example").is_err());
+        assert!(parse("3 --stdin --input x.txt").is_err());
+    }
+
+    #[test]
+    fn test_day_ranges() {
+        let days = |line| match parse(line) {
+            Ok(Command::Run(args)) => Some(args.days),
+            _ => None,
+        };
+        assert_eq!(days("all"), Some(Days::Range(1, 25)));
+        assert_eq!(days("run all --example"), Some(Days::Range(1, 25)));
+        assert_eq!(days("1..=10"), Some(Days::Range(1, 10)));
+        assert_eq!(days("run 3..6"), Some(Days::Range(3, 5)));
+        assert_eq!(days("1..26"), Some(Days::Range(1, 25)));
+        assert_eq!(days("1..27"), None);
+        assert_eq!(days("1..1"), None);
+        assert_eq!(days("run 6..3"), None);
+        assert_eq!(days("all --input x.txt"), None);
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("run").is_err());
+        assert!(parse("run 26").is_err());
+        assert!(parse("run 3 --part 3").is_err());
+        assert!(parse("run 3 --part").is_err());
+        assert!(parse("run 3 example").is_err());
+        assert!(parse("run 3 --example=yes").is_err());
+        assert!(parse("run 3 --example --input x.txt").is_err());
+        assert!(parse("run 3 --format yaml").is_err());
+        assert!(parse("run 3 --heatmap out.ppm").is_err());
+        assert!(parse("run 11 --animate --fps 0").is_err());
+        assert!(parse("run 11 --animate --fps fast").is_err());
+        assert!(parse("run 11 --headless").is_err());
+        assert!(parse("run 15 --frame-skip 10").is_err());
+        assert!(parse("run 12 --format json --debug").is_err());
+        assert!(parse("run 11 --animate --format=json").is_err());
+        assert!(parse("run 14 --steps -1").is_err());
+        assert!(parse("run 13 --steps 5").is_err());
+        assert!(parse("run 14 --steps 5 --part 1").is_err());
+    }
+}
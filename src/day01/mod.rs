@@ -1,39 +1,43 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error, ErrorKind, Read};
+use std::io::{BufRead, BufReader, Read};
 
-fn read_ints<R: Read>(io: R) -> Result<Vec<usize>, Error> {
+use crate::error::{empty_input, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
+
+/// Read one depth measurement per line.
+pub fn read_ints<R: Read>(io: R) -> Result<Vec<usize>> {
     let br = BufReader::new(io);
     let mut values = vec![];
-    for line in br.lines() {
-        values.push(
-            line?
-                .trim()
-                .parse()
-                .map_err(|e| Error::new(ErrorKind::InvalidData, e))?,
-        );
+    for (i, line) in br.lines().enumerate() {
+        let line = line?;
+        let input_line = InputLine::new(1, i + 1, &line);
+        values.push(input_line.parse(line.trim())?);
+    }
+    if values.is_empty() {
+        return Err(empty_input(1));
     }
     Ok(values)
 }
 
-pub fn run() -> Result<(), Error> {
-    let puzzle_input = read_ints(File::open("inputs/day01.txt")?)?;
-
+/// Count how many depth measurements are larger than the previous one.
+pub fn part1(puzzle_input: &[usize]) -> usize {
     let mut cur_depth: usize = puzzle_input[0];
     let mut count_increases: usize = 0;
 
-    for line in &puzzle_input {
+    for line in puzzle_input {
         if line > &cur_depth {
             count_increases += 1;
         }
         cur_depth = *line;
     }
-    println!("Part 1 -- Total depth increases: {}", count_increases);
+    count_increases
+}
 
-    // println!("len puzzle_input: {}", puzzle_input.len());
-    count_increases = 0;
+/// Same as part 1, but comparing the sums of a sliding window of 3 measurements.
+pub fn part2(puzzle_input: &[usize]) -> usize {
+    let mut count_increases = 0;
     let mut prev_sum = 0;
     for window in puzzle_input.windows(3) {
-        let cur_sum = window.into_iter().sum::<usize>();
+        let cur_sum = window.iter().sum::<usize>();
         if prev_sum == 0 {
             // naïve way to get first sum. Rust is hard.
             prev_sum = cur_sum;
@@ -46,9 +50,24 @@ pub fn run() -> Result<(), Error> {
         // print!("WINDOW {:?}", window);
         // println!(" -- SUM {:?}", &window.into_iter().sum::<usize>());
     }
-    println!(
-        "Part 2 -- Total depth increases (sliding window of 3 values): {}",
-        count_increases
-    );
-    Ok(())
+    count_increases
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_ints(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part2(input).into())
+    }
 }
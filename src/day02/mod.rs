@@ -1,80 +1,130 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error, Read};
+use std::io::{BufRead, BufReader, Read};
 
-fn read_input<R: Read>(io: R) -> Result<Vec<(String, i32)>, Error> {
+use crate::error::{AocError, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Up,
+    Down,
+}
+
+/// A `(direction, distance)` pair like `(Forward, 5)`.
+pub type Instruction = (Direction, i32);
+
+/// Read one instruction (like `forward 5`) per line.
+pub fn read_input<R: Read>(io: R) -> Result<Vec<Instruction>> {
     let br = BufReader::new(io);
     let mut values = vec![];
-    for line in br.lines() {
-        let line_inner = line.unwrap();
-        let split: Vec<&str> = line_inner.split(' ').collect();
-        values.push((split[0].to_string(), split[1].parse().expect("OH NOES")))
+    for (i, line) in br.lines().enumerate() {
+        let line_inner = line?;
+        let input_line = InputLine::new(2, i + 1, &line_inner);
+        let (direction, distance) = line_inner
+            .split_once(' ')
+            .ok_or_else(|| input_line.error_at(1, "expected '<direction> <distance>'"))?;
+        let direction = match direction {
+            "forward" => Direction::Forward,
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            _ => {
+                return Err(
+                    input_line.error(direction, format!("invalid direction '{}'", direction))
+                )
+            }
+        };
+        values.push((direction, input_line.parse(distance)?))
     }
     Ok(values)
 }
 
+/// Position of the submarine. Distances are `i32`, but a few long moves add up to more than that,
+/// so positions are `i64` -- and the moves return `None` if even that overflows.
 #[derive(Default)]
 struct Submarine {
-    h_pos: i32,
-    v_pos: i32,
-    aim: i32,
+    h_pos: i64,
+    v_pos: i64,
+    aim: i64,
 }
 
 impl Submarine {
-    fn move_part1(&mut self, instruction: (String, i32)) {
-        let (direction, distance) = instruction;
-        match direction.as_str() {
-            "forward" => self.h_pos += distance,
-            "up" => self.v_pos -= distance,
-            "down" => self.v_pos += distance,
-            &_ => println!("Invalid instruction: ({:?}, {:?})", direction, distance),
+    fn move_part1(&mut self, instruction: &Instruction) -> Option<()> {
+        let (direction, distance) = (instruction.0, i64::from(instruction.1));
+        match direction {
+            Direction::Forward => self.h_pos = self.h_pos.checked_add(distance)?,
+            Direction::Up => self.v_pos = self.v_pos.checked_sub(distance)?,
+            Direction::Down => self.v_pos = self.v_pos.checked_add(distance)?,
         }
+        Some(())
     }
 
-    fn move_part2(&mut self, instruction: (String, i32)) {
-        let (direction, distance) = instruction;
-        match direction.as_str() {
-            "forward" => {
-                self.h_pos += distance;
-                self.v_pos += self.aim * distance
+    fn move_part2(&mut self, instruction: &Instruction) -> Option<()> {
+        let (direction, distance) = (instruction.0, i64::from(instruction.1));
+        match direction {
+            Direction::Forward => {
+                self.h_pos = self.h_pos.checked_add(distance)?;
+                self.v_pos = self.v_pos.checked_add(self.aim.checked_mul(distance)?)?
             }
-            "up" => self.aim -= distance,
-            "down" => self.aim += distance,
-            &_ => println!("Invalid instruction: ({:?}, {:?})", direction, distance),
+            Direction::Up => self.aim = self.aim.checked_sub(distance)?,
+            Direction::Down => self.aim = self.aim.checked_add(distance)?,
         }
+        Some(())
     }
 
-    fn display_pos(&self) {
-        println!("X/Y: {} -- Z: {}", self.h_pos, self.v_pos);
+    /// Product of the horizontal position and depth.
+    fn position_product(&self) -> Option<i64> {
+        self.h_pos.checked_mul(self.v_pos)
     }
+}
+
+fn overflow() -> AocError {
+    AocError::solve(2, "submarine position overflowed")
+}
 
-    fn surface(&mut self) {
-        self.h_pos = 0;
-        self.v_pos = 0;
-        self.aim = 0;
+/// Final horizontal position times depth, where `up`/`down` change the depth directly.
+pub fn part1(instructions: &[Instruction]) -> Result<i64> {
+    let mut sub: Submarine = Default::default();
+    for instruction in instructions {
+        // println!("Moving -> {:?}", instruction);
+        sub.move_part1(instruction).ok_or_else(overflow)?;
     }
+    sub.position_product().ok_or_else(overflow)
 }
 
-pub fn run() -> Result<(), Error> {
-    let puzzle_input = read_input(File::open("inputs/day02.txt")?)?;
-    // println!("{:?}", puzzle_input);
+/// Final horizontal position times depth, where `up`/`down` change the aim.
+pub fn part2(instructions: &[Instruction]) -> Result<i64> {
     let mut sub: Submarine = Default::default();
+    for instruction in instructions {
+        sub.move_part2(instruction).ok_or_else(overflow)?;
+    }
+    sub.position_product().ok_or_else(overflow)
+}
 
-    println!("PART 1:");
-    for instruction in &puzzle_input {
-        // println!("Moving -> {:?}", instruction);
-        sub.move_part1(instruction.clone());
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input.as_bytes())
     }
-    sub.display_pos();
-    println!("{}\n", sub.h_pos * sub.v_pos);
 
-    sub.surface();
+    fn part1(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part1(input)?.into())
+    }
 
-    println!("PART 2:");
-    for instruction in puzzle_input {
-        sub.move_part2(instruction);
+    fn part2(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part2(input)?.into())
     }
-    sub.display_pos();
-    println!("{}\n", sub.h_pos * sub.v_pos);
+}
 
-    Ok(())
+#[test]
+fn test_long_moves() {
+    let far = read_input("forward 2147483647\nforward 2147483647\ndown 2\n".as_bytes()).unwrap();
+    assert_eq!(part1(&far).unwrap(), 4294967294 * 2);
+    // aiming down steeply enough, depth times horizontal position no longer fits in an i64
+    let deep =
+        read_input("down 2147483647\ndown 2147483647\nforward 2147483647\n".as_bytes()).unwrap();
+    assert!(part2(&deep).is_err());
 }
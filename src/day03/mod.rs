@@ -1,26 +1,51 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error, ErrorKind, Read};
+use std::io::{BufRead, BufReader, Read};
 
-fn read_input<R: Read>(io: R) -> Result<Vec<Vec<u32>>, Error> {
+use crate::error::{empty_input, AocError, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
+
+/// Widest row we can read: the product of two ratings this wide still fits an `Answer`.
+const MAX_BITS: usize = 31;
+
+/// Read the diagnostic report as rows of binary digits.
+pub fn read_input<R: Read>(io: R) -> Result<Vec<Vec<u32>>> {
     let br = BufReader::new(io);
-    let mut values = vec![];
-    for line in br.lines() {
-        let line_inner = line.unwrap();
-        let nums: Vec<u32> = line_inner
+    let mut values: Vec<Vec<u32>> = vec![];
+    for (i, line) in br.lines().enumerate() {
+        let line_inner = line?;
+        let input_line = InputLine::new(3, i + 1, &line_inner);
+        let nums = line_inner
             .chars()
-            .map(|c| c.to_digit(10).expect("invalid char"))
-            .collect();
+            .enumerate()
+            .map(|(col, c)| input_line.digit(col, c, 2))
+            .collect::<Result<Vec<u32>>>()?;
+        if nums.len() > MAX_BITS {
+            return Err(input_line.error_at(
+                MAX_BITS + 1,
+                format!("rows can be at most {} bits wide", MAX_BITS),
+            ));
+        }
+        if let Some(first) = values.first() {
+            if nums.len() != first.len() {
+                return Err(input_line.error_at(
+                    1,
+                    format!("expected {} bits, found {}", first.len(), nums.len()),
+                ));
+            }
+        }
         values.push(nums);
     }
+    if values.is_empty() {
+        return Err(empty_input(3));
+    }
     Ok(values)
 }
 
 // Take in HashMap tuple values, return binary strings representing filter by
 // most significant bit & least significant bit.
-fn get_bits_from_tuples(arr: &mut Vec<(usize, i32)>) -> String {
+fn get_bits_from_tuples(arr: &mut [(usize, i32)]) -> String {
     let mut max_string = String::new();
-    arr.sort_by(|a, b| a.0.cmp(&b.0));
+    arr.sort_by_key(|a| a.0);
     for tup in arr {
         if tup.1 > 0 {
             max_string.push('1');
@@ -32,13 +57,13 @@ fn get_bits_from_tuples(arr: &mut Vec<(usize, i32)>) -> String {
 }
 
 // Part 1: Count bits from 2D array columns
-fn count_bits_in_columns(array_2d: &Vec<Vec<u32>>, cols: usize) -> Vec<(usize, i32)> {
+fn count_bits_in_columns(array_2d: &[Vec<u32>], cols: usize) -> Vec<(usize, i32)> {
     let mut cnt_map = HashMap::new();
     for row in array_2d {
-        for i in 0..cols {
-            if row[i] == 1 {
+        for (i, bit) in row.iter().enumerate().take(cols) {
+            if *bit == 1 {
                 *cnt_map.entry(i).or_insert(0) += 1;
-            } else if row[i] == 0 {
+            } else if *bit == 0 {
                 *cnt_map.entry(i).or_insert(0) -= 1;
             }
         }
@@ -49,7 +74,7 @@ fn count_bits_in_columns(array_2d: &Vec<Vec<u32>>, cols: usize) -> Vec<(usize, i
 // Part 2
 // Get most or least common value (0 or 1) from a column of binary digits.
 fn get_most_or_least_common_value(
-    array_2d: &Vec<Vec<u32>>,
+    array_2d: &[Vec<u32>],
     column: usize,
     most_or_least: &str,
 ) -> u32 {
@@ -58,7 +83,11 @@ fn get_most_or_least_common_value(
         *cnt_map.entry(row[column]).or_insert(0) += 1u32;
     }
     let mut items: Vec<(u32, u32)> = cnt_map.into_iter().collect();
-    items.sort_by(|a, b| a.1.cmp(&b.1));
+    items.sort_by_key(|a| a.1);
+    if items.len() == 1 {
+        // every remaining row has the same bit in this column
+        return items[0].0;
+    }
     match most_or_least {
         "most" => {
             if items[0].1 == items[1].1 {
@@ -79,25 +108,19 @@ fn get_most_or_least_common_value(
     }
 }
 
-// Perform operations to solve part1 of day03 puzzle
-fn part1(most_common: String, cols: usize) -> Result<u32, Error> {
-    let gamma = u32::from_str_radix(most_common.as_str(), 2)
-        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+// Multiply gamma (most common bits) by epsilon (least common bits)
+fn power_consumption(most_common: String, cols: usize) -> Result<u64> {
+    let gamma = u64::from_str_radix(most_common.as_str(), 2)
+        .map_err(|e| AocError::solve(3, format!("no most common bits ({})", e)))?;
     // Perform bitwise NOT on gamma value to get inverse of all bits for epsilon value
     // 01101 -> 10010
-    let epsilon = !gamma & 2u32.pow(cols as u32) - 1;
-    println!(
-        "gamma:   0b{0:0>cols$b} ({0})\nepsilon: 0b{1:0>cols$b} ({1})",
-        gamma,
-        epsilon,
-        cols = cols
-    );
+    let epsilon = !gamma & (2u64.pow(cols as u32) - 1);
     Ok(gamma * epsilon)
 }
 
 // Get oxygen generator rating || co2 scrubber rating for part 2 of day03 puzzle
-fn part2(array_2d: &Vec<Vec<u32>>, cols: usize, o2_or_co2: &str) -> u32 {
-    let mut filter_arr = array_2d.clone();
+fn rating(array_2d: &[Vec<u32>], cols: usize, o2_or_co2: &str) -> u64 {
+    let mut filter_arr = array_2d.to_vec();
     for i in 0..cols {
         let choice = match o2_or_co2 {
             "o2" => "most",
@@ -113,31 +136,59 @@ fn part2(array_2d: &Vec<Vec<u32>>, cols: usize, o2_or_co2: &str) -> u32 {
             break;
         }
     }
-    let mut final_rating = 0u32;
-    for i in &filter_arr[0] {
+    let mut final_rating = 0u64;
+    for &i in &filter_arr[0] {
         final_rating <<= 1;
-        final_rating |= i;
+        final_rating |= u64::from(i);
     }
-    println!(
-        "{: >3} rating: {:0>cols$b}",
-        o2_or_co2,
-        final_rating,
-        cols = cols
-    );
     final_rating
 }
 
-pub fn run() -> Result<(), Error> {
-    let array_2d = read_input(File::open("inputs/day03.txt")?)?;
+/// Part 1: power consumption of the submarine.
+pub fn part1(array_2d: &[Vec<u32>]) -> Result<u64> {
     let cols = array_2d[0].len();
-
-    let mut items = count_bits_in_columns(&array_2d, cols);
+    let mut items = count_bits_in_columns(array_2d, cols);
     let max = get_bits_from_tuples(&mut items);
-    println!("Part 1 result: {}\n", part1(max, cols)?);
+    power_consumption(max, cols)
+}
 
-    let o2_rating = part2(&array_2d, cols, "o2");
-    let co2_rating = part2(&array_2d, cols, "co2");
-    println!("Part 2 result: {}", o2_rating * co2_rating);
+/// Part 2: life support rating (oxygen generator rating * CO2 scrubber rating).
+pub fn part2(array_2d: &[Vec<u32>]) -> u64 {
+    let cols = array_2d[0].len();
+    rating(array_2d, cols, "o2") * rating(array_2d, cols, "co2")
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Vec<Vec<u32>>;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input.as_bytes())
+    }
+
+    fn part1(array_2d: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part1(array_2d)?.into())
+    }
+
+    fn part2(array_2d: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part2(array_2d).into())
+    }
+}
+
+#[test]
+fn test_wide_rows() {
+    let widest = format!(
+        "{}\n{}\n{}\n",
+        "1".repeat(31),
+        "0".repeat(31),
+        "1".repeat(31)
+    );
+    let report = read_input(widest.as_bytes()).unwrap();
+    assert_eq!(part1(&report).unwrap(), 0);
+    assert_eq!(rating(&report, 31, "o2"), (1 << 31) - 1);
+    assert_eq!(part2(&report), 0);
+    let too_wide = format!("{}\n", "1".repeat(32));
+    assert!(read_input(too_wide.as_bytes()).is_err());
 }
@@ -1,38 +1,63 @@
 use std::collections::hash_map::DefaultHasher;
 use std::fmt;
-use std::fs::File;
 use std::hash::{Hash, Hasher};
-use std::io::{BufRead, BufReader, Error, Read};
+use std::io::{BufRead, BufReader, Read};
+
+use crate::error::{empty_input, AocError, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
+
+type Rows = Vec<[u32; 5]>;
 
 /// Trying to learn how to consume buffers instead of just converting it to
 /// String on the heap and working with .split()...
-fn read_input<R: Read>(io: R) -> Result<(Vec<u32>, Vec<String>), Error> {
+///
+/// Returns the drawn numbers and the bingo boards.
+pub fn read_input<R: Read>(io: R) -> Result<(Vec<u32>, Vec<Board>)> {
     let mut br = BufReader::new(io);
 
     let mut first_line = String::new();
-    br.read_line(&mut first_line)?;
-    // skip the next line -- it's a \n
-    br.read_line(&mut first_line)?;
-    first_line = first_line.trim().to_string();
-
-    let nums: Vec<u32> = first_line.split(',').map(|v| v.parse().unwrap()).collect();
-    first_line.clear();
-
-    let mut cur_board = String::new();
-    let mut raw_boards: Vec<String> = vec![];
-    for line in br.lines() {
-        let inner = line.unwrap();
-        if inner.len() == 0 {
-            raw_boards.push(cur_board.clone());
-            cur_board.clear();
-        }
-        cur_board += format!("{}\n", inner).as_str();
+    if br.read_line(&mut first_line)? == 0 {
+        return Err(empty_input(4));
     }
-    if cur_board.len() != 0 {
-        // missing the last block. Rust is hard.
-        raw_boards.push(cur_board);
+    let first_line = first_line.trim_end();
+    let input_line = InputLine::new(4, 1, first_line);
+    let nums = first_line
+        .split(',')
+        .map(|v| input_line.parse(v.trim()))
+        .collect::<Result<Vec<u32>>>()?;
+
+    let mut boards = vec![];
+    let mut rows: Rows = vec![];
+    // Boards are separated by blank lines -- finish the current one on each blank line and at the end
+    let mut finish_board = |rows: &mut Rows, line: &InputLine| -> Result<()> {
+        match rows.len() {
+            0 => {}
+            5 => boards.push(Board::from_rows(rows)),
+            n => return Err(line.error_at(1, format!("board has {} rows, expected 5", n))),
+        }
+        rows.clear();
+        Ok(())
+    };
+    let mut last_line = (1, first_line.to_string());
+    for (i, line) in br.lines().enumerate() {
+        let inner = line?;
+        let input_line = InputLine::new(4, i + 2, &inner);
+        if inner.trim().is_empty() {
+            finish_board(&mut rows, &input_line)?;
+        } else {
+            let row = inner
+                .split_whitespace()
+                .map(|v| input_line.parse(v))
+                .collect::<Result<Vec<u32>>>()?;
+            let row = row.try_into().map_err(|row: Vec<u32>| {
+                input_line.error_at(1, format!("expected 5 numbers, found {}", row.len()))
+            })?;
+            rows.push(row);
+        }
+        last_line = (i + 2, inner);
     }
-    Ok((nums, raw_boards))
+    finish_board(&mut rows, &InputLine::new(4, last_line.0, &last_line.1))?;
+    Ok((nums, boards))
 }
 
 /// Bingo board!
@@ -43,7 +68,7 @@ fn read_input<R: Read>(io: R) -> Result<(Vec<u32>, Vec<String>), Error> {
 /// - flag is 0 or 1 to denote a pip on that square
 /// - value is the value of the square
 #[derive(Clone, Copy)]
-struct Board {
+pub struct Board {
     squares: [[(u8, u32); 5]; 5],
     winner: bool,
     id: u64,
@@ -63,9 +88,9 @@ impl Board {
     /// Search board for number and flag that square's tuple
     fn update_board_with_number(&mut self, num: u32) {
         for row in &mut self.squares {
-            for i in 0..row.len() {
-                if row[i].1 == num {
-                    row[i].0 = 1;
+            for square in row.iter_mut() {
+                if square.1 == num {
+                    square.0 = 1;
                 }
             }
         }
@@ -76,21 +101,16 @@ impl Board {
         self.squares[y][x] = (0u8, val);
     }
 
-    /// Create a board from a newline-separated `&String` buffer.
-    fn from(buf: &String) -> Board {
+    /// Create a board from its 5 rows of numbers.
+    pub fn from_rows(rows: &[[u32; 5]]) -> Board {
         let mut board = Board::new();
-        let mut y = 0usize;
         let mut values = vec![];
 
-        for line in buf.trim().split('\n') {
-            let mut spl: Vec<&str> = line.trim().split(' ').collect::<Vec<&str>>();
-            spl.retain(|c| c.len() != 0);
-            let nums: Vec<u32> = spl.into_iter().map(|v| v.trim().parse().unwrap()).collect();
-            for x in 0..5 {
-                board.insert(x, y, nums[x]);
-                values.push(nums[x]);
+        for (y, row) in rows.iter().take(5).enumerate() {
+            for (x, num) in row.iter().enumerate() {
+                board.insert(x, y, *num);
+                values.push(*num);
             }
-            y += 1;
         }
         // update self.id with hash value
         let mut s = DefaultHasher::new();
@@ -120,7 +140,8 @@ impl Board {
         }
     }
 
-    fn calculate_board_product(&self) -> u32 {
+    /// Sum of unmarked squares times the number that made this board win.
+    pub fn calculate_board_product(&self) -> u32 {
         let mut unmarked = 0;
         for row in self.squares {
             unmarked += row
@@ -157,16 +178,16 @@ impl fmt::Display for Board {
 /// Call `update_board_with_number` for all bingo boards & check for any new winners.
 ///
 /// Returns a winning board if there is a new winner.
-fn call_bingo(boards: &mut Vec<Board>, number: u32) -> Option<&mut Board> {
+fn call_bingo(boards: &mut [Board], number: u32) -> Option<&mut Board> {
     // Though there may be multiple winners for a single round/number, part 2 only cares about the
     // last winning board, so it doesn't matter if this `mut winner` gets overwritten several times here
     let mut winner: Option<&mut Board> = None;
 
     for board in boards {
-        if !board.winner == true {
+        if !board.winner {
             board.update_board_with_number(number);
             board.check_for_win();
-            if board.winner == true {
+            if board.winner {
                 board.winning_number = number;
                 winner = Some(board)
             }
@@ -176,42 +197,47 @@ fn call_bingo(boards: &mut Vec<Board>, number: u32) -> Option<&mut Board> {
     winner
 }
 
-pub fn run(example: bool) -> Result<(), Error> {
-    let input_file = if example {
-        "inputs/day04_example.txt"
-    } else {
-        "inputs/day04.txt"
-    };
-    let (nums, raw_boards) = read_input(File::open(input_file)?)?;
-    let mut boards: Vec<Board> = raw_boards.iter().map(|b| Board::from(b)).collect();
-    println!("PLAY BINGO!! *COUGH* *COUGH* (so much smoke in this submarine...)");
-
-    let mut winner_ids: Vec<u64> = Vec::new();
-
-    for number in &nums {
-        let bingo = call_bingo(&mut boards, *number);
-        match bingo {
-            Some(board) => {
-                if winner_ids.len() == 0 {
-                    // Part 1
-                    println!("First Win! {}", board);
-                    let product = board.calculate_board_product();
-                    println!("Part 1 answer: {}\n=============\n", product);
-                }
-                if !winner_ids.contains(&board.id) {
-                    winner_ids.push(board.id)
-                }
-            }
-            None => continue,
+/// Part 1: score of the first board to win.
+pub fn part1(nums: &[u32], boards: &[Board]) -> Option<u32> {
+    // PLAY BINGO!! *COUGH* *COUGH* (so much smoke in this submarine...)
+    let mut boards = boards.to_vec();
+    for number in nums {
+        if let Some(board) = call_bingo(&mut boards, *number) {
+            return Some(board.calculate_board_product());
+        }
+    }
+    None
+}
+
+/// Part 2: score of the last board to win.
+pub fn part2(nums: &[u32], boards: &[Board]) -> Option<u32> {
+    let mut boards = boards.to_vec();
+    let mut last_board = None;
+    for number in nums {
+        if let Some(board) = call_bingo(&mut boards, *number) {
+            last_board = Some(*board);
         }
     }
-    // Part 2
-    let last_board = boards
-        .iter()
-        .find(|b| &b.id == winner_ids.last().unwrap())
-        .unwrap();
-    println!("Last board to win: {}", last_board);
-    println!("Part 2 answer: {}", last_board.calculate_board_product());
-
-    Ok(())
+    last_board.map(|board| board.calculate_board_product())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = (Vec<u32>, Vec<Board>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input.as_bytes())
+    }
+
+    fn part1((nums, boards): &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        let score = part1(nums, boards).ok_or_else(|| AocError::solve(4, "no board won"))?;
+        Ok(score.into())
+    }
+
+    fn part2((nums, boards): &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        let score = part2(nums, boards).ok_or_else(|| AocError::solve(4, "no board won"))?;
+        Ok(score.into())
+    }
 }
@@ -1,18 +1,23 @@
 use std::cmp::{max, min};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error, Read};
+use std::io::{BufRead, BufReader, Read};
+
+use crate::error::{InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
 
 /// Create line segments from Day 05 puzzle input.
-fn read_input<R: Read>(io: R) -> Result<Vec<LineSegment>, Error> {
+pub fn read_input<R: Read>(io: R) -> Result<Vec<LineSegment>> {
     let br = BufReader::new(io);
     let mut line_segments: Vec<LineSegment> = vec![];
-    for line in br.lines() {
-        let inner = line.unwrap();
-        let parts = inner.split(" -> ").into_iter().collect::<Vec<&str>>();
+    for (i, line) in br.lines().enumerate() {
+        let inner = line?;
+        let input_line = InputLine::new(5, i + 1, &inner);
+        let (p1, p2) = inner
+            .split_once(" -> ")
+            .ok_or_else(|| input_line.error_at(1, "expected 'x1,y1 -> x2,y2'"))?;
         line_segments.push(LineSegment::from_points(
-            Point::from_str(&parts[0]),
-            Point::from_str(&parts[1]),
+            Point::parse(&input_line, p1)?,
+            Point::parse(&input_line, p2)?,
         ))
     }
 
@@ -20,46 +25,44 @@ fn read_input<R: Read>(io: R) -> Result<Vec<LineSegment>, Error> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Point {
+pub struct Point {
     x: i32,
     y: i32,
 }
 
 impl Point {
-    fn new(x: i32, y: i32) -> Self {
+    pub fn new(x: i32, y: i32) -> Self {
         Point { x, y }
     }
 
-    /// Return an instance of `Point` from string formatted like `"9,2"`
-    fn from_str(string: &str) -> Self {
-        let coords = string
-            .split(',')
-            .collect::<Vec<&str>>()
-            .iter()
-            .map(|v| v.parse().expect("invalid"))
-            .collect::<Vec<i32>>();
-        Point {
-            x: coords[0],
-            y: coords[1],
-        }
+    /// Return an instance of `Point` from string formatted like `"9,2"`,
+    /// where `string` is part of the input `line`.
+    fn parse(line: &InputLine, string: &str) -> Result<Self> {
+        let (x, y) = string
+            .split_once(',')
+            .ok_or_else(|| line.error(string, format!("expected 'x,y', found '{}'", string)))?;
+        Ok(Point {
+            x: line.parse(x)?,
+            y: line.parse(y)?,
+        })
     }
 
     /// Return a tuple (i32, i32) representing this points coordinates.
-    fn as_tup(&self) -> (i32, i32) {
+    pub fn as_tup(&self) -> (i32, i32) {
         (self.x, self.y)
     }
 }
 
 #[derive(Debug)]
-struct LineSegment {
+pub struct LineSegment {
     p1: Point,
     p2: Point,
 }
 
 impl LineSegment {
     /// Create line from two `Point` structs, sorting the points left->right by x value
-    fn from_points(pt1: Point, pt2: Point) -> Self {
-        let mut pts = vec![pt1, pt2];
+    pub fn from_points(pt1: Point, pt2: Point) -> Self {
+        let mut pts = [pt1, pt2];
         pts.sort_by_key(|p| p.x);
         LineSegment {
             p1: pts[0],
@@ -70,7 +73,7 @@ impl LineSegment {
     /// Get line slope using classic `(y1 - y2) / (x1 - x2)` formula.
     ///
     /// Returns `None` if slope is undefined because line segment is vertical (x1 == x2).
-    fn slope(&self) -> Option<i32> {
+    pub fn slope(&self) -> Option<i32> {
         if self.p1.x - self.p2.x == 0 {
             // avoid division by 0
             return None;
@@ -81,23 +84,20 @@ impl LineSegment {
     /// Return y-intercept for a `LineSegment` (if line is not vertical).
     ///
     /// Solves for `y = mx + b` as `b = y - mx`.
-    fn y_intercept(&self) -> Option<i32> {
-        match self.slope() {
-            Some(m) => {
-                if m == 0 {
-                    Some(self.p1.y)
-                } else {
-                    Some(self.p1.y - (m * self.p1.x))
-                }
+    pub fn y_intercept(&self) -> Option<i32> {
+        self.slope().map(|m| {
+            if m == 0 {
+                self.p1.y
+            } else {
+                self.p1.y - (m * self.p1.x)
             }
-            None => None,
-        }
+        })
     }
 
     /// Return a Vector of `Point` objects representing integer coordinates along a line segment.
     ///
     /// Uses `y = mx + b` formula where applicable.
-    fn gen_coords(&self) -> Vec<Point> {
+    pub fn gen_coords(&self) -> Vec<Point> {
         let mut coords: Vec<Point> = vec![];
 
         let m = match self.slope() {
@@ -121,49 +121,40 @@ impl LineSegment {
     }
 }
 
-pub fn run(part: usize, input: Option<&String>) -> Result<(), Error> {
-    let input_path = match input {
-        Some(arg) => {
-            if arg == "example" {
-                "inputs/day05_example.txt"
-            } else {
-                println!(
-                    "{:?} is not a valid arg (try 'example'). Using default input.",
-                    arg
-                );
-                "inputs/day05.txt"
+/// Count the points where at least two lines overlap.
+///
+/// Part 1 only wants horizontal/vertical lines, part 2 wants _all_ lines.
+pub fn count_overlaps(line_segments: &[LineSegment], part: usize) -> usize {
+    let mut counter = HashMap::new();
+
+    for line in line_segments {
+        if let Some(m) = line.slope() {
+            if m != 0 && part == 1 {
+                continue;
             }
         }
-        _ => "inputs/day05.txt",
-    };
-    let line_segments = read_input(File::open(input_path)?)?;
-    let mut part1_counter = HashMap::new();
-
-    for line in &line_segments {
-        // Part 1 only wants horizontal/vertical lines
-        // Part 2 wants _all_ lines
-        match line.slope() {
-            Some(m) => {
-                if m == 0 {
-                    (); // note: () in control flow like this is essentially emulating a "noop"
-                } else {
-                    if part == 1 {
-                        continue;
-                    }
-                    ();
-                }
-            }
-            None => (),
-        };
         for coord in line.gen_coords() {
-            *part1_counter.entry(coord.as_tup()).or_insert(0) += 1;
+            *counter.entry(coord.as_tup()).or_insert(0) += 1;
         }
     }
-    let more_than_1_intersection = part1_counter.iter().filter(|&(_, v)| *v > 1).count();
-    println!(
-        "Part {}\n  Number of points where at least 2 lines overlap: {}",
-        part, more_than_1_intersection
-    );
+    counter.iter().filter(|&(_, v)| *v > 1).count()
+}
+
+pub struct Day05;
 
-    Ok(())
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Vec<LineSegment>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(count_overlaps(input, 1).into())
+    }
+
+    fn part2(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(count_overlaps(input, 2).into())
+    }
 }
@@ -1,16 +1,22 @@
 use std::collections::HashMap;
-use std::fs::read_to_string;
+
+use crate::error::{empty_input, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
 
 const STATE_ARR: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 0];
 
-/// Create line segments from Day 05 puzzle input.
-fn parse_input(file_name: &str) -> Vec<u8> {
-    let line = read_to_string(file_name).expect("file not found");
-    line.trim()
-        .split(",")
-        .collect::<Vec<&str>>()
-        .iter()
-        .map(|v| v.parse().unwrap())
+/// Parse lanternfish timers (single line of comma-separated integers).
+pub fn parse_input(line: &str) -> Result<Vec<u8>> {
+    let line = InputLine::new(6, 1, line.trim_end());
+    if line.text.is_empty() {
+        return Err(empty_input(6));
+    }
+    line.text
+        .split(',')
+        .map(|v| match line.parse(v)? {
+            timer @ 0..=8 => Ok(timer),
+            _ => Err(line.error(v, "timers must be between 0 and 8")),
+        })
         .collect()
 }
 
@@ -33,17 +39,17 @@ fn calculate_grow(state: &mut HashMap<u8, u64>, days: u32) -> HashMap<u8, u64> {
     let mut new_state: HashMap<u8, u64> = HashMap::new();
     if days > 0 {
         for i in STATE_ARR {
-            if i == 0 && state.get(&i).is_some() {
+            if i == 0 && state.contains_key(&i) {
                 // zero counters spawn new fish (8)
                 new_state.insert(8, *state.get(&i).unwrap());
-                if new_state.get(&6u8).is_some() {
+                if new_state.contains_key(&6u8) {
                     // if there were any existing 6 counters, add the new resets to them
                     *new_state.entry(6u8).or_default() += *state.get(&i).unwrap();
                 } else {
                     // otherwise, all the recent resets become 6 counters
                     *new_state.entry(6u8).or_default() = *state.get(&i).unwrap();
                 }
-            } else if i > 0 && state.get(&i).is_some() {
+            } else if i > 0 && state.contains_key(&i) {
                 // for values besides 0, shift all the previous day's counters down by one
                 *new_state.entry(i - 1u8).or_default() = *state.get(&i).unwrap();
             }
@@ -54,28 +60,42 @@ fn calculate_grow(state: &mut HashMap<u8, u64>, days: u32) -> HashMap<u8, u64> {
     state.to_owned()
 }
 
-pub fn run(part: usize, example: bool) {
-    let input_path = match example {
-        true => "inputs/day06_example.txt",
-        false => "inputs/day06.txt",
-    };
-    let lanternfish = parse_input(input_path);
-
-    let days = match part {
-        0 => 18,
-        1 => 80,
-        2 => 256,
-        _ => panic!("Part should be 0 (testing/example) 1 or 2"),
-    };
-
+/// Total number of lanternfish after `days` days.
+pub fn count_fish(lanternfish: &[u8], days: u32) -> u64 {
     let mut initial_state: HashMap<u8, u64> = HashMap::new();
 
-    for item in &lanternfish {
+    for item in lanternfish {
         *initial_state.entry(*item).or_insert(0) += 1;
     }
 
     let result_state = calculate_grow(&mut initial_state, days);
     // println!("final result_state - {:?}", result_state);
-    let num_fish: u64 = result_state.values().sum();
-    println!("Total fish after {} days: {:?}", days, num_fish);
+    result_state.values().sum()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(count_fish(input, 80).into())
+    }
+
+    fn part2(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(count_fish(input, 256).into())
+    }
+}
+
+#[test]
+fn test_timer_range() {
+    assert_eq!(parse_input("3,4,3,1,2\n").unwrap(), [3, 4, 3, 1, 2]);
+    assert_eq!(parse_input("0,8\n").unwrap(), [0, 8]);
+    let err = parse_input("3,10,3\n").unwrap_err().to_string();
+    assert!(err.contains("column 3"), "{}", err);
 }
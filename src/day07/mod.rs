@@ -1,12 +1,19 @@
-use std::fs::read_to_string;
+use crate::error::{empty_input, AocError, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
 
-/// Parse day07 input file (single line of comma-separated integers)
-fn parse_input(file_name: &str) -> Vec<i32> {
-    let line = read_to_string(file_name).expect("file not found");
-    line.trim().split(",").map(|v| v.parse().unwrap()).collect()
+/// Parse day07 input (single line of comma-separated integers)
+pub fn parse_input(line: &str) -> Result<Vec<i32>> {
+    let line = InputLine::new(7, 1, line.trim_end());
+    if line.text.is_empty() {
+        return Err(empty_input(7));
+    }
+    line.text.split(',').map(|v| line.parse(v)).collect()
 }
 
-fn get_median(vec: &mut Vec<i32>) -> i32 {
+// Positions are parsed as `i32`, but distances and fuel are `i64`: crabs can be more than
+// `i32::MAX` apart, and fuel grows with the square of the distance.
+
+fn get_median(vec: &mut [i32]) -> i64 {
     if vec.is_empty() {
         return 0;
     }
@@ -15,32 +22,34 @@ fn get_median(vec: &mut Vec<i32>) -> i32 {
     let index = vec.len() / 2;
 
     if vec.len() % 2 == 1 {
-        vec[index]
+        vec[index].into()
     } else {
-        (vec[index - 1] + vec[index]) / 2
+        (i64::from(vec[index - 1]) + i64::from(vec[index])) / 2
     }
 }
 
-fn get_mean(vec: &mut Vec<i32>) -> i32 {
+fn get_mean(vec: &mut [i32]) -> i64 {
     if vec.is_empty() {
         return 0;
     }
     vec.sort();
-    let float_mean: f32 = vec.iter().sum::<i32>() as f32 / vec.len() as f32;
+    let float_mean: f64 = vec.iter().map(|&c| i64::from(c)).sum::<i64>() as f64 / vec.len() as f64;
     // println!("{} rounds to -> {}", float_mean, float_mean.round());
-    float_mean.round() as i32
+    float_mean.round() as i64
 }
 
 /// Calculate total distance (fuel consumed) for all crabs to a given position
 /// using Gaussian summation method
-fn gaussian_distance(pos: i32, crabs: &Vec<i32>) -> i32 {
+fn gaussian_distance(pos: i64, crabs: &[i32]) -> Result<i64> {
+    let overflow = || AocError::solve(7, "fuel cost overflowed");
     crabs
         .iter()
         // get distance of each crab relative to `pos`
-        .map(|c| (c - pos).abs())
+        .map(|&c| (i64::from(c) - pos).abs())
         // get gaussian sum of each relative distance
-        .map(|n| (n * (n + 1)) / 2)
-        .sum::<i32>()
+        .map(|n| n.checked_mul(n + 1).map(|sum| sum / 2))
+        .try_fold(0i64, |total, fuel| total.checked_add(fuel?))
+        .ok_or_else(overflow)
 }
 
 /// Solution adapted from [here](https://www.ericburden.work/blog/2021/12/07/advent-of-code-2021-day-7/)
@@ -51,14 +60,14 @@ fn gaussian_distance(pos: i32, crabs: &Vec<i32>) -> i32 {
 /// distances for each crab to that position.
 /// Then we move the position left or right depending for which direction the total fuel/gaussian sum is lower
 /// until we see the total fuel start to increase again.
-fn part2(crabs: &mut Vec<i32>) -> i32 {
+pub fn part2(crabs: &mut [i32]) -> Result<i64> {
     let pos = get_mean(crabs);
-    let mut min_fuel = gaussian_distance(pos, &crabs);
+    let mut min_fuel = gaussian_distance(pos, crabs)?;
 
     // Find which direction we need to move from the mean to get to a lower fuel cost (if there is one)
     let (leftdist, rightdist) = (
-        gaussian_distance(pos - 1, &crabs),
-        gaussian_distance(pos + 1, &crabs),
+        gaussian_distance(pos - 1, crabs)?,
+        gaussian_distance(pos + 1, crabs)?,
     );
     let (mut pos, mut nextdist, step) = match leftdist < rightdist {
         true => (pos - 1, leftdist, -1),
@@ -72,34 +81,47 @@ fn part2(crabs: &mut Vec<i32>) -> i32 {
         if nextdist < min_fuel {
             min_fuel = nextdist;
             pos += step;
-            nextdist = gaussian_distance(pos, crabs)
+            nextdist = gaussian_distance(pos, crabs)?
         } else {
             break;
         }
     }
-    min_fuel
+    Ok(min_fuel)
 }
 
-pub fn run(example: Option<&String>) {
-    let input_path = match example {
-        Some(s) => {
-            if s == "example" {
-                "inputs/day07_example.txt"
-            } else {
-                "inputs/day07.txt"
-            }
-        }
-        _ => "inputs/day07.txt",
-    };
-    let mut positions = parse_input(input_path);
+/// Find median position and calculate sum of all crabs movement to that position
+pub fn part1(crabs: &mut [i32]) -> i64 {
+    let median = get_median(crabs);
+    crabs.iter().map(|&i| (i64::from(i) - median).abs()).sum()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<i32>;
 
-    // Part 1
-    // Find median position and calculate sum of all crabs movement to that position
-    let median = get_median(&mut positions);
-    let fuel_amt: i32 = positions.iter().map(|i| (i - median).abs()).sum();
-    println!("Part 1 - fuel amount: {}", fuel_amt);
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part1(&mut input.clone()).into())
+    }
 
-    // Part 2
-    let min_fuel = part2(&mut positions);
-    println!("Part 2 - fuel amount: {}", min_fuel);
+    fn part2(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part2(&mut input.clone())?.into())
+    }
+}
+
+#[test]
+fn test_far_apart_crabs() {
+    let mut crabs = parse_input("2147483647,0\n").unwrap();
+    assert_eq!(part1(&mut crabs), 2147483647);
+    // both crabs meet halfway: 1073741823 + 1073741824 steps
+    let fuel = |n: i64| n * (n + 1) / 2;
+    assert_eq!(
+        part2(&mut crabs).unwrap(),
+        fuel(1073741823) + fuel(1073741824)
+    );
 }
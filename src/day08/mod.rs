@@ -1,11 +1,15 @@
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{self, prelude::*, BufReader};
+
+use crate::error::{lines, AocError, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
+
+/// One display: its ten unique signal patterns and the four output digits.
+pub type Entry = (Vec<String>, Vec<String>);
 
 /// Parse day08 input file (lines consisting of 7 space-separated strings of chars a-g, delimeter `'|'` and 4 space-separated strings of chars a-g)
 /// representative of digital number display (when working correctly):
-/// ```
+/// ```text
 ///   0:      1:      2:      3:      4:
 ///   aaaa    ....    aaaa    aaaa    ....
 ///  b    c  .    c  .    c  .    c  b    c
@@ -24,29 +28,47 @@ use std::io::{self, prelude::*, BufReader};
 ///  .    f  e    f  .    f  e    f  .    f
 ///   gggg    gggg    ....    gggg    gggg
 /// ```
-fn parse_input(path: &str) -> Result<Vec<(Vec<String>, Vec<String>)>, io::Error> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+pub fn parse_input(string: &str) -> Result<Vec<Entry>> {
     let mut input_output = vec![];
 
-    for line in reader.lines() {
-        let inner: Vec<String> = line?.split(" | ").map(|a| a.to_string()).collect();
-        let input = inner[0]
-            .split(' ')
-            .map(|word| word.chars().sorted().collect::<String>())
-            .collect();
-        let output = inner[1]
-            .split(' ')
-            .map(|word| word.chars().sorted().collect::<String>())
-            .collect();
-        input_output.push((input, output));
+    for line in lines(8, string) {
+        let (input, output) = line
+            .text
+            .split_once(" | ")
+            .ok_or_else(|| line.error_at(1, "expected patterns and output separated by ' | '"))?;
+        input_output.push((
+            parse_patterns(&line, input, 10)?,
+            parse_patterns(&line, output, 4)?,
+        ));
     }
     Ok(input_output)
 }
 
-fn part1(outputs: &Vec<Vec<String>>) {
+/// Parse `count` space-separated patterns of segments a-g, each sorted so equal patterns compare equal.
+fn parse_patterns(line: &InputLine, patterns: &str, count: usize) -> Result<Vec<String>> {
+    let words: Vec<&str> = patterns.split(' ').collect();
+    if words.len() != count {
+        return Err(line.error(
+            patterns,
+            format!("expected {} patterns, found {}", count, words.len()),
+        ));
+    }
+    words
+        .into_iter()
+        .map(
+            |word| match word.find(|c: char| !('a'..='g').contains(&c)) {
+                Some(i) => Err(line.error(&word[i..], "segments must be one of a-g")),
+                None if word.is_empty() => Err(line.error(word, "empty pattern")),
+                None => Ok(word.chars().sorted().collect::<String>()),
+            },
+        )
+        .collect()
+}
+
+/// Count the output digits that are a 1, 4, 7 or 8.
+pub fn part1(display: &[Entry]) -> usize {
     let mut counter = HashMap::new();
-    for output in outputs {
+    for (_, output) in display {
         for key in output {
             *counter.entry(key).or_insert(0) += 1;
         }
@@ -59,26 +81,24 @@ fn part1(outputs: &Vec<Vec<String>>) {
             total_uniq += tup.1;
         }
     }
-    println!("Total unique outputs (Part 1): {}", total_uniq);
+    total_uniq
 }
 
-fn part2(inputs: &Vec<Vec<String>>, outputs: &Vec<Vec<String>>) {
+/// Decode every display and sum the output values.
+pub fn part2(display: &[Entry]) -> Result<u32> {
     let mut total_sum = 0;
-    for i in 0..inputs.len() {
-        let (mut inp, out) = (inputs[i].to_owned(), outputs[i].to_owned());
+    for (i, (input, out)) in display.iter().enumerate() {
+        let undecodable = || AocError::solve(8, format!("can't decode display on line {}", i + 1));
+        let mut inp = input.to_owned();
         let mut num_map = HashMap::new();
-        populate_num_map(&mut inp, &mut num_map);
-        let final_num = u32::from_str_radix(
-            out.iter()
-                .map(|v| num_map.get(v).unwrap().to_string())
-                .collect::<String>()
-                .as_str(),
-            10,
-        )
-        .unwrap();
+        populate_num_map(&mut inp, &mut num_map).ok_or_else(undecodable)?;
+        let mut final_num = 0;
+        for v in out {
+            final_num = final_num * 10 + *num_map.get(v).ok_or_else(undecodable)? as u32;
+        }
         total_sum += final_num;
     }
-    println!("Part 2 - total_sum: {}", total_sum);
+    Ok(total_sum)
 }
 
 /// Had to do this a really ugly way. Tried multiple other methods for filtering, and just couldn't
@@ -88,10 +108,12 @@ fn part2(inputs: &Vec<Vec<String>>, outputs: &Vec<Vec<String>>) {
 ///   1. consume each item using `.remove` as items are filtered...
 ///   2. using HashSet logic to determine which display number is mapped to each string
 ///   3. modify `num_map` HashMap in-place to create string->display digit mapping
-fn populate_num_map(input: &mut Vec<String>, num_map: &mut HashMap<String, u8>) {
+///
+/// Returns `None` if the signals don't describe a (scrambled) seven-segment display.
+fn populate_num_map(input: &mut Vec<String>, num_map: &mut HashMap<String, u8>) -> Option<()> {
     input.sort_by_key(|word| word.len());
     // println!("Input sorted: {:?}", input);
-    num_map.insert(input.pop().unwrap(), 8);
+    num_map.insert(input.pop()?, 8);
     let one = input.remove(0);
     num_map.insert(one.clone(), 1);
     num_map.insert(input.remove(0), 7);
@@ -113,6 +135,7 @@ fn populate_num_map(input: &mut Vec<String>, num_map: &mut HashMap<String, u8>)
                         continue 'outer;
                     }
                 }
+                return None;
             }
             5 => {
                 for i in 3..5 {
@@ -123,9 +146,10 @@ fn populate_num_map(input: &mut Vec<String>, num_map: &mut HashMap<String, u8>)
                         continue 'outer;
                     }
                 }
+                return None;
             }
             4 => {
-                nine = input.pop().unwrap();
+                nine = input.pop()?;
                 num_map.insert(nine.clone(), 9);
                 continue 'outer;
             }
@@ -139,6 +163,7 @@ fn populate_num_map(input: &mut Vec<String>, num_map: &mut HashMap<String, u8>)
                         continue 'outer;
                     }
                 }
+                return None;
             }
             2 => {
                 for i in 0..2 {
@@ -149,35 +174,32 @@ fn populate_num_map(input: &mut Vec<String>, num_map: &mut HashMap<String, u8>)
                         continue 'outer;
                     }
                 }
+                return None;
             }
             1 => {
-                num_map.insert(input.pop().unwrap(), 2);
-                break;
+                num_map.insert(input.pop()?, 2);
+                return Some(());
             }
-            _ => panic!(),
+            _ => return None,
         }
     }
 }
 
-pub fn run(example: bool) -> io::Result<()> {
-    let file_path = if example == true {
-        "inputs/day08_example.txt"
-    } else {
-        "inputs/day08.txt"
-    };
+pub struct Day08;
 
-    let display = parse_input(file_path).expect("Couldn't parse input file");
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Vec<Entry>;
 
-    let mut inputs = vec![];
-    let mut outputs = vec![];
-    for item in display {
-        inputs.push(item.0);
-        outputs.push(item.1);
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
     }
 
-    part1(&outputs);
-
-    part2(&inputs, &outputs);
+    fn part1(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part1(input).into())
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part2(input)?.into())
+    }
 }
@@ -1,200 +1,156 @@
 use itertools::Itertools;
-use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Error};
-
-// Example data
-// const ARR_COLS: usize = 10;
-// const ARR_ROWS: usize = 5;
-// Puzzle data
-const ARR_COLS: usize = 100;
-const ARR_ROWS: usize = 100;
-const ADJACENT: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
-type LavaGrid = [[u8; ARR_COLS]; ARR_ROWS];
-
-fn parse_input(path: &str) -> Result<LavaGrid, Error> {
-    let mut lava_grid = [[0; ARR_COLS]; ARR_ROWS];
-    let mut f = BufReader::new(
-        File::open(path).unwrap_or_else(|e| panic!("file open failed: {} - {}", e, path)),
-    );
-
-    let mut row = 0;
-    let mut buf = String::new();
-    loop {
-        match f.read_line(&mut buf) {
-            Ok(bytes_read) => {
-                if bytes_read == 0 {
-                    break;
-                }
-                let digits: Vec<u8> = buf
-                    .trim()
-                    .chars()
-                    .map(|c| c.to_digit(10).unwrap().try_into().unwrap())
-                    .collect();
-                for i in 0..ARR_COLS {
-                    lava_grid[row][i] = digits[i];
-                }
-                row += 1;
-                buf.clear();
-            }
-            Err(err) => return Err(err),
-        };
-    }
-    Ok(lava_grid)
-}
 
-fn get_adjacent(x: usize, y: usize) -> Vec<(usize, usize)> {
-    let mut coords = vec![];
-    for pt in ADJACENT {
-        let (new_x, new_y) = (x as i32 + pt.0, y as i32 + pt.1);
-        if new_x < 0 || new_y < 0 || new_x >= ARR_COLS as i32 || new_y >= ARR_ROWS as i32 {
-            // edge or corner
-            continue;
-        }
-        coords.push((new_x as usize, new_y as usize));
-    }
+use crate::error::Result;
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, RunConfig, Solution};
+
+pub type LavaGrid = Grid<u8>;
 
-    coords
+/// Parse the heightmap; its size is taken from the input (rows of equal numbers of digits).
+pub fn parse_input(input: &str) -> Result<LavaGrid> {
+    Grid::parse_digits(9, input)
 }
 
-fn find_low_points(lava_grid: &LavaGrid) -> Vec<u32> {
+/// Heights of every point lower than all of its (4-way) neighbors.
+pub fn find_low_points(lava_grid: &LavaGrid) -> Vec<u32> {
     let mut low_pts = vec![];
-    for y in 0..ARR_ROWS {
-        for x in 0..ARR_COLS {
-            let cur_val = lava_grid[y][x];
-            let adjacent = get_adjacent(x, y);
-            let mut is_low_point: bool = true;
-            for (pt_x, pt_y) in adjacent {
-                if lava_grid[pt_y][pt_x] <= cur_val {
-                    is_low_point = false;
-                    break;
-                }
-            }
-            if is_low_point {
-                low_pts.push(cur_val as u32);
-            }
+    for (pos, &cur_val) in lava_grid.iter() {
+        if lava_grid
+            .neighbors4(pos)
+            .all(|neighbor| lava_grid[neighbor] > cur_val)
+        {
+            low_pts.push(cur_val as u32);
         }
     }
     low_pts
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Node {
-    x: usize,
-    y: usize,
-    val: u8,
-    filled: bool,
+/// Basins are areas surrounded by (walls of) 9s.
+fn is_boundary(height: u8) -> bool {
+    height == 9
 }
 
-impl Node {
-    fn new(x: usize, y: usize, val: u8) -> Self {
-        Node {
-            x,
-            y,
-            val,
-            filled: false,
-        }
-    }
-
-    fn fill(&mut self) {
-        self.filled = true;
-    }
-
-    fn is_boundary(&self) -> bool {
-        self.val == 9
-    }
+/// An area of the heightmap surrounded by (walls of) 9s, draining into its low point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Basin {
+    pub id: usize,
+    pub low_point: Pos,
+    pub cells: Vec<Pos>,
+}
 
-    fn get_adjacent(&self) -> Vec<(usize, usize)> {
-        get_adjacent(self.x, self.y)
+impl Basin {
+    pub fn size(&self) -> usize {
+        self.cells.len()
     }
 }
 
-#[derive(Debug)]
-struct Grid {
-    grid: [[Node; ARR_COLS]; ARR_ROWS],
-    basins: HashSet<Vec<u8>>,
+/// Every basin in a heightmap, along with which basin each point belongs to.
+#[derive(Debug, Clone)]
+pub struct Basins {
+    basins: Vec<Basin>,
+    /// Basin id of every point; `None` for the 9s between basins.
+    ids: Grid<Option<usize>>,
 }
 
-impl Grid {
-    fn from_lava_grid(arr: &LavaGrid) -> Self {
-        let mut grid = [[Node::new(0, 0, 0); ARR_COLS]; ARR_ROWS];
-        for y in 0..ARR_ROWS {
-            for x in 0..ARR_COLS {
-                grid[y][x] = Node::new(x, y, arr[y][x]);
+impl Basins {
+    /// Flood fill each basin in turn. The fill keeps its own stack rather than recursing, so
+    /// large basins can't overflow the call stack.
+    pub fn find(lava_grid: &LavaGrid) -> Self {
+        let mut basins = vec![];
+        let mut ids = lava_grid.map(|_| None);
+        for start in lava_grid.positions() {
+            if ids[start].is_some() || is_boundary(lava_grid[start]) {
+                continue;
             }
-        }
-        Grid {
-            grid,
-            basins: HashSet::new(),
-        }
-    }
-
-    fn fill_basin(&mut self, x: usize, y: usize, basin: &mut Vec<u8>) {
-        let cur_node = self.get_node_at_xy(x, y);
-        if !cur_node.filled && !cur_node.is_boundary() {
-            basin.push(cur_node.val);
-            cur_node.fill();
-            for (pt_x, pt_y) in cur_node.get_adjacent() {
-                self.fill_basin(pt_x, pt_y, basin)
+            let id = basins.len();
+            let mut cells = vec![];
+            let mut stack = vec![start];
+            ids[start] = Some(id);
+            while let Some(pos) = stack.pop() {
+                cells.push(pos);
+                for neighbor in lava_grid.neighbors4(pos) {
+                    if ids[neighbor].is_none() && !is_boundary(lava_grid[neighbor]) {
+                        ids[neighbor] = Some(id);
+                        stack.push(neighbor);
+                    }
+                }
             }
-        } else {
-            return ();
+            let low_point = *cells
+                .iter()
+                .min_by_key(|&&pos| lava_grid[pos])
+                .expect("a basin holds at least its first point");
+            basins.push(Basin {
+                id,
+                low_point,
+                cells,
+            });
         }
+        Basins { basins, ids }
     }
 
-    fn get_node_at_xy(&mut self, x: usize, y: usize) -> &mut Node {
-        &mut self.grid[y][x]
+    /// All basins, ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = &Basin> {
+        self.basins.iter()
     }
 
-    fn find_basins(&mut self) {
-        let mut visited: HashSet<Node> = HashSet::new();
-        for y in 0..ARR_ROWS {
-            for x in 0..ARR_COLS {
-                let node = self.get_node_at_xy(x, y);
-                if visited.contains(node) || node.is_boundary() {
-                    continue;
-                }
-                visited.insert(self.get_node_at_xy(x, y).clone());
-                let mut basin = vec![];
-                self.fill_basin(x, y, &mut basin);
-                // not sure why ending up with some empty basin vectors...
-                if !basin.is_empty() {
-                    self.basins.insert(basin.to_owned());
-                }
-            }
-        }
+    /// The basin `pos` belongs to; `None` for 9s and positions outside the heightmap.
+    pub fn at(&self, pos: Pos) -> Option<&Basin> {
+        let id = (*self.ids.get(pos)?)?;
+        self.basins.get(id)
     }
 }
 
-pub fn run(example: bool) {
-    let path = if example == true {
-        "inputs/day09_example.txt"
-    } else {
-        "inputs/day09.txt"
-    };
-    let lava_grid = parse_input(path).expect("Error reading puzzle input");
-
-    // Part 1
-    let low_pts = find_low_points(&lava_grid);
-    let sum_low_pts: u32 = low_pts.iter().map(|v| v + 1).sum();
-    println!(
-        "Part 1 - weighted (+1) sum of low points:   {}",
-        sum_low_pts
-    );
-
-    // Part 2
-    let mut grid = Grid::from_lava_grid(&lava_grid);
-    grid.find_basins();
-
-    let mut product = 1;
-    for basin in grid
-        .basins
-        .into_iter()
-        .sorted_by_key(|f| f.len())
+/// Part 2: multiply together the sizes of the 3 largest basins.
+pub fn part2(lava_grid: &LavaGrid) -> usize {
+    Basins::find(lava_grid)
+        .iter()
+        .map(Basin::size)
+        .sorted()
         .rev()
         .take(3)
-    {
-        product *= basin.len();
+        .product()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = LavaGrid;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    /// Weighted (+1) sum of low points.
+    fn part1(lava_grid: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        let low_pts = find_low_points(lava_grid);
+        Ok(low_pts.iter().map(|v| v + 1).sum::<u32>().into())
     }
-    println!("Part 2 - product of 3 largest basin values: {}", product);
+
+    fn part2(lava_grid: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part2(lava_grid).into())
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE_INPUT: &str = "\
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+";
+
+#[test]
+fn test_basins() {
+    let lava_grid = parse_input(EXAMPLE_INPUT).unwrap();
+    let basins = Basins::find(&lava_grid);
+    let sizes: Vec<usize> = basins.iter().map(Basin::size).collect();
+    assert_eq!(sizes, [3, 9, 14, 9]);
+    let basin = basins.at((2, 2)).unwrap();
+    assert_eq!((basin.low_point, basin.size()), ((2, 2), 14));
+    assert_eq!(basins.at((9, 1)).unwrap().low_point, (9, 0));
+    assert!(basins.at((2, 0)).is_none());
+    assert!(basins.at((10, 0)).is_none());
+    assert_eq!(part2(&lava_grid), 1134);
 }
@@ -1,21 +1,29 @@
 use std::collections::HashMap;
-use std::fs::File;
 use std::hash::Hash;
-use std::io::{BufRead, BufReader};
+
+use crate::error::{lines, AocError, Result};
+use crate::solution::{Answer, RunConfig, Solution};
 
 static OPEN_SYMBOLS: [char; 4] = ['<', '(', '{', '['];
 static CLOSE_SYMBOLS: [char; 4] = ['>', ')', '}', ']'];
 static PAIRS: [(char, char); 4] = [('>', '<'), (')', '('), ('}', '{'), (']', '[')];
 
-fn parse_input(path: &str) -> Vec<Vec<char>> {
-    let reader =
-        BufReader::new(File::open(path).unwrap_or_else(|e| panic!("Error opening file: {}", e)));
+/// Parse the navigation subsystem: lines made up only of the bracket symbols `()[]{}<>`.
+pub fn parse_input(string: &str) -> Result<Vec<Vec<char>>> {
     let mut parsed = vec![];
 
-    for line in reader.lines() {
-        parsed.push(line.unwrap().chars().collect())
+    for line in lines(10, string) {
+        if let Some((col, c)) = line
+            .text
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !OPEN_SYMBOLS.contains(c) && !CLOSE_SYMBOLS.contains(c))
+        {
+            return Err(line.error_at(col + 1, format!("invalid symbol '{}'", c)));
+        }
+        parsed.push(line.text.chars().collect())
     }
-    parsed
+    Ok(parsed)
 }
 
 /// Swap key/value pairs of arbitrary type and return a new `HashMap`.
@@ -30,14 +38,14 @@ fn swap_hashmap<T: Copy, U: Eq + Hash + Copy>(map: &HashMap<T, U>) -> HashMap<U,
 
 /// Solve part one of Day 10 AoC 2021.
 ///
-/// Returns a tuple of (incomplete lines: `Vec<Vec<char>>`, and symbols on which a corrupt line is stopped: Vec<char>).
-fn part1(nav_sys: Vec<Vec<char>>, pairs: &HashMap<char, char>) -> (Vec<Vec<char>>, Vec<char>) {
+/// Returns a tuple of (incomplete lines: `Vec<Vec<char>>`, and symbols on which a corrupt line is stopped: `Vec<char>`).
+pub fn part1(nav_sys: &[Vec<char>], pairs: &HashMap<char, char>) -> (Vec<Vec<char>>, Vec<char>) {
     // let expected_symbols = swap_hashmap(&pairs);
 
     let mut incomplete = vec![];
 
     let mut corrupted = vec![];
-    'lines: for line in &nav_sys {
+    'lines: for line in nav_sys {
         let mut open = vec![];
         for c in line {
             if OPEN_SYMBOLS.contains(c) {
@@ -45,12 +53,10 @@ fn part1(nav_sys: Vec<Vec<char>>, pairs: &HashMap<char, char>) -> (Vec<Vec<char>
             } else if CLOSE_SYMBOLS.contains(c) {
                 let match_sym = match open.pop() {
                     Some(sym) => *sym,
-                    None => {
-                        println!("No symbols in queue");
-                        continue;
-                    }
+                    // No symbols in queue
+                    None => continue,
                 };
-                let expected_sym = *pairs.get(&c).expect("Key error");
+                let expected_sym = *pairs.get(c).expect("Key error");
                 if match_sym != expected_sym {
                     #[rustfmt::skip]
                     // println!("Expected '{}', but found '{}' instead.", expected_symbols.get(&match_sym).unwrap(), c);
@@ -66,61 +72,97 @@ fn part1(nav_sys: Vec<Vec<char>>, pairs: &HashMap<char, char>) -> (Vec<Vec<char>
 }
 
 /// In `fn part1(...)`, only the remaining open brackets are returned, making it easier to match those pairs.
-fn part2(incompletes: &mut Vec<Vec<char>>, pairs: &HashMap<char, char>) -> u64 {
+pub fn part2(incompletes: &mut Vec<Vec<char>>, pairs: &HashMap<char, char>) -> Result<u64> {
     let mut scores: Vec<u64> = vec![];
-    while !incompletes.is_empty() {
+    while let Some(mut line) = incompletes.pop() {
         let mut closers: Vec<char> = vec![];
-        let mut line = incompletes.pop().unwrap();
-        while !line.is_empty() {
-            let c = line.pop().unwrap();
-            closers.push(*pairs.get(&c).unwrap());
+        while let Some(c) = line.pop() {
+            let closer = pairs
+                .get(&c)
+                .ok_or_else(|| AocError::solve(10, format!("no closing symbol for '{}'", c)))?;
+            closers.push(*closer);
         }
-        scores.push(calculate_autocomplete_score(closers))
+        scores.push(calculate_autocomplete_score(closers)?)
+    }
+    if scores.is_empty() {
+        return Err(AocError::solve(10, "no incomplete lines"));
     }
     scores.sort();
-    scores[scores.len() / 2]
+    Ok(scores[scores.len() / 2])
 }
 
-fn calculate_autocomplete_score(symbols: Vec<char>) -> u64 {
-    let mut total_score = 0;
+pub fn calculate_autocomplete_score(symbols: Vec<char>) -> Result<u64> {
+    let mut total_score: u64 = 0;
     for c in symbols {
-        total_score *= 5;
-        total_score += match c {
+        let score = match c {
             ')' => 1,
             ']' => 2,
             '}' => 3,
             '>' => 4,
-            _ => panic!("invalid character: '{}'", c),
-        }
+            _ => {
+                return Err(AocError::solve(
+                    10,
+                    format!("invalid closing symbol '{}'", c),
+                ))
+            }
+        };
+        total_score = total_score
+            .checked_mul(5)
+            .and_then(|total| total.checked_add(score))
+            .ok_or_else(|| AocError::solve(10, "autocomplete score overflowed"))?;
+    }
+    Ok(total_score)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    /// Syntax error score of the corrupted lines.
+    fn part1(navigation: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        let pairs = HashMap::from(PAIRS);
+        let (_, corrupted) = part1(navigation, &pairs);
+        let sum: u32 = corrupted
+            .iter()
+            .map(|c| match c {
+                ')' => 3,
+                ']' => 57,
+                '}' => 1197,
+                '>' => 25137,
+                _ => unreachable!("invalid char: '{}'", c),
+            })
+            .sum();
+        Ok(sum.into())
+    }
+
+    /// Middle autocomplete score of the incomplete lines.
+    fn part2(navigation: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        let pairs = HashMap::from(PAIRS);
+        let (mut incomplete, _) = part1(navigation, &pairs);
+        let pairs = swap_hashmap(&pairs); // swap '>': '<' for '<': '>'
+        Ok(part2(&mut incomplete, &pairs)?.into())
     }
-    total_score
 }
 
-pub fn run(example: bool) {
-    let path = if example == true {
-        "inputs/day10_example.txt"
-    } else {
-        "inputs/day10.txt"
-    };
-    let navigation = parse_input(path);
-
-    // Part 1
-    let mut pairs = HashMap::from(PAIRS);
-    let (mut incomplete, corrupted) = part1(navigation, &pairs);
-    let sum: u32 = corrupted
-        .iter()
-        .map(|c| match c {
-            ')' => 3,
-            ']' => 57,
-            '}' => 1197,
-            '>' => 25137,
-            _ => panic!("invalid char: '{}'", c),
-        })
-        .sum();
-    println!("Part 1 - corrupt character score: {}", sum);
-
-    // Part 2
-    pairs = swap_hashmap(&pairs); // swap '>': '<' for '<': '>'
-    let score = part2(&mut incomplete, &pairs);
-    println!("Part 2 - autocompleter score: {}", score);
+#[test]
+fn test_no_incomplete_lines() {
+    let config = RunConfig::default();
+    let corrupted = parse_input("(]\n<>{)\n").unwrap();
+    assert_eq!(Day10::part1(&corrupted, &config).unwrap(), 60u32.into());
+    assert!(Day10::part2(&corrupted, &config).is_err());
+    assert!(Day10::part2(&vec![], &config).is_err());
+    assert_eq!(
+        calculate_autocomplete_score(vec![']', ')', '}', '>']).unwrap(),
+        294
+    );
+    assert!(calculate_autocomplete_score(vec!['(']).is_err());
+    // 27 '>'s still fit in a u64, 28 don't
+    assert!(calculate_autocomplete_score(vec!['>'; 27]).is_ok());
+    assert!(calculate_autocomplete_score(vec!['>'; 28]).is_err());
 }
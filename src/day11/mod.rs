@@ -1,156 +1,42 @@
-use lazy_static::lazy_static;
-use std::{env, fmt};
-use std::{thread, time};
-
-const PUZZLE_INPUT: &str = "\
-6617113584
-6544218638
-5457331488
-1135675587
-1221353216
-1811124378
-1387864368
-4427637262
-6778645486
-3682146745
-";
-
-const EXAMPLE_INPUT: &str = "\
-5483143223
-2745854711
-5264556173
-6141336146
-6357385478
-4167524645
-2176841721
-6882881134
-4846848554
-5283751526
-";
-
-lazy_static! {
-    static ref DEBUG: bool = if env::var("DEBUG").unwrap_or("false".to_string()) == "true" {
-        true
-    } else {
-        false
-    };
-}
-
-static ADJACENT: [(isize, isize); 8] = [
-    (1, 0),
-    (1, 1),
-    (0, 1),
-    (-1, 1),
-    (-1, 0),
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-];
-
-/// Allows for printing animated grid state inside each cycle
-fn clear_screen() {
-    print!("{esc}c", esc = 27 as char);
-}
-
-#[derive(Debug, Default, Clone, Copy)]
-struct Node {
-    x: usize,
-    y: usize,
-    val: u8,
-}
-
-impl Node {
-    /// Return all neighboring (x, y) coordinates relative to this Nodes x & y position.
-    fn get_surrounding_coords(&self, w_bound: usize, h_bound: usize) -> Vec<(usize, usize)> {
-        let mut surrounding = vec![];
-        for (dx, dy) in ADJACENT {
-            let y1 = self.y as isize + dy;
-            let x1 = self.x as isize + dx;
-            if y1 >= 0 && x1 >= 0 && y1 < h_bound as isize && x1 < w_bound as isize {
-                surrounding.push((x1 as usize, y1 as usize))
-            }
-        }
-        surrounding
-    }
-
-    /// "Flash" this node and return whether or not a flash occured
-    fn flash(&mut self) -> bool {
-        if self.val > 9 {
-            self.val = 0;
-            return true;
-        }
-        false
-    }
-
-    /// Mutate internal state by incrementing value += 1
-    fn charge(&mut self) {
-        self.val += 1;
-    }
-}
-
-/// Rust lifetimes, Rc, RefCell<T>, and Vec<Rc<RefCell<T>>> are difficult to
-/// wrap one's head around. So, this implementation is a Grid which **OWNS** all `Node` struct
-/// objects in its 2D Vector, and references those nodes by index[y][x] position.
+use std::fmt;
+use std::time::Duration;
+
+use crate::animation::{Animation, Text};
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::solution::{Answer, RunConfig, Solution};
+
+/// Frames per second while octopuses are flashing
+const FPS: f64 = 30.0;
+/// How long the grid is shown at the end of each step...
+const STEP_PAUSE: Duration = Duration::from_millis(800);
+/// ...and before the first one.
+const INITIAL_PAUSE: Duration = Duration::from_millis(1500);
+
+/// Energy levels of the octopuses in the cavern.
 ///
-/// When the state of a `Node` needs to be mutated, it's retrieved via `&mut self.nodes[y][x]`.
-struct Grid {
-    w: usize,
-    h: usize,
-    nodes: Vec<Vec<Node>>,
-}
-
-/// Allow cloning so Part 1 & Part 2 can have fresh state.
-impl Clone for Grid {
-    fn clone(&self) -> Self {
-        let mut new_nodes = vec![];
-        for row in self.nodes.iter() {
-            let mut new_row = vec![];
-            for node in row {
-                new_row.push(node.clone());
-            }
-            new_nodes.push(new_row);
-        }
-        Grid {
-            w: self.w,
-            h: self.h,
-            nodes: new_nodes,
-        }
-    }
+/// An octopus with an energy level above 9 flashes, which resets it to 0 and charges all 8
+/// octopuses around it.
+#[derive(Debug, Clone)]
+pub struct Octopuses {
+    energy: Grid<u8>,
 }
 
-/// Create a grid of `Node` struct objects from input string.
-impl From<String> for Grid {
-    fn from(string: String) -> Self {
-        let mut y = 0;
-        let mut x = 0;
-        let mut nodes = vec![];
-        for line in string.lines() {
-            let mut row = vec![];
-            for c in line.chars() {
-                let val = c.to_digit(10).unwrap() as u8;
-                row.push(Node { x, y, val });
-                x += 1;
-            }
-            nodes.push(row);
-            y += 1;
-            x = 0;
-        }
-
-        Grid {
-            w: nodes[0].len(),
-            h: nodes.len(),
-            nodes,
-        }
+impl Octopuses {
+    /// Parse rows of energy levels (digits).
+    pub fn parse(input: &str) -> Result<Self> {
+        Ok(Octopuses {
+            energy: Grid::parse_digits(11, input)?,
+        })
     }
 }
 
 /// Display the grid with all "flashed" (0) nodes as bold red.
-impl fmt::Display for Grid {
+impl fmt::Display for Octopuses {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut string = String::new();
-        for y in 0..self.h {
-            for x in 0..self.w {
-                let val = self.nodes[y][x].val;
+        for row in self.energy.rows() {
+            for &val in row {
                 let bold = if val == 0 || val > 9 {
                     "\x1b[0;91m\x1b[1m"
                 } else {
@@ -162,103 +48,175 @@ impl fmt::Display for Grid {
                     string.push_str(format!("{}#\x1b[0m", bold).as_str());
                 }
             }
-            string.push_str("\n");
+            string.push('\n');
         }
-        write!(f, "{}\n", string)
+        writeln!(f, "{}", string)
     }
 }
 
-impl Grid {
-    /// For number of `cycles` (steps), charge & flash Octopuses, counting total number of flashes.
-    /// Short-circuit in the case that all octopuses flash in one cycle (synchronized), returning which
-    /// step number that occured.
-    fn count_flashes(&mut self, cycles: usize) -> usize {
-        // DEBUG flag allows pretty-printing of the grid per-step -- fun to watch
-        let milliseconds = time::Duration::from_millis(30);
-        let seconds = time::Duration::from_millis(800);
-
-        let mut total_flashes = 0;
-        for i in 0..cycles {
-            let mut flashes_this_cycle = 0;
-            let mut to_flash = vec![];
-
-            // first step: Increment value of all nodes by one
-            for node in self.nodes.iter_mut().flatten() {
-                node.charge();
-                // if any node's value is > 9, it's gonna POP
-                if node.val > 9 {
-                    to_flash.push((node.x, node.y));
-                }
+impl Octopuses {
+    /// Charge & flash every Octopus once, returning the number of flashes during this step.
+    ///
+    /// With an `animation`, the grid is drawn after every flash -- fun to watch.
+    fn step(&mut self, i: usize, mut animation: Option<&mut Animation>) -> usize {
+        let mut flashes_this_cycle = 0;
+        let mut to_flash = vec![];
+        // first step: Increment value of all nodes by one
+        for (pos, val) in self.energy.positions().zip(self.energy.cells_mut()) {
+            *val += 1;
+            // if any node's value is > 9, it's gonna POP
+            if *val > 9 {
+                to_flash.push(pos);
             }
-
-            // Pop a node (octopus) that is ready to flash out of the queue
-            while let Some((x, y)) = to_flash.pop() {
-                let cur_node = &mut self.nodes[y][x];
-                if cur_node.val == 0 {
-                    // already flashed
-                    continue;
-                }
-                if cur_node.flash() {
-                    // Increment two counters:
-                    // `flashes_this_cycle` is to determine whether or not all have flashed in sync (all zeroes)
-                    flashes_this_cycle += 1;
-                    total_flashes += 1;
-
-                    // Increment value (charge) of each neighboring node, adding it into flash queue if it's value is > 9
-                    for (x, y) in cur_node.get_surrounding_coords(self.w, self.h) {
-                        let nxt_node = &mut self.nodes[y][x];
-                        if nxt_node.val != 0 {
-                            nxt_node.charge();
-                            if nxt_node.val > 9 {
-                                to_flash.push((x, y));
-                            }
-                        }
+        }
+        // Pop a node (octopus) that is ready to flash out of the queue
+        while let Some(pos) = to_flash.pop() {
+            if self.energy[pos] == 0 {
+                // already flashed
+                continue;
+            }
+            // "Flash" this node
+            self.energy[pos] = 0;
+            // `flashes_this_cycle` is also used to determine whether or not all have flashed in sync (all zeroes)
+            flashes_this_cycle += 1;
+            // Increment value (charge) of each neighboring node, adding it into flash queue if it's value is > 9
+            let neighbors: Vec<_> = self.energy.neighbors8(pos).collect();
+            for next in neighbors {
+                let nxt_val = &mut self.energy[next];
+                if *nxt_val != 0 {
+                    *nxt_val += 1;
+                    if *nxt_val > 9 {
+                        to_flash.push(next);
                     }
                 }
-                // Print out grid as you go
-                if *DEBUG {
-                    println!("{}", self);
-                    clear_screen();
-                    thread::sleep(milliseconds);
-                }
             }
-            // Print out grid at end of step
-            if *DEBUG {
-                println!("{}\nSTEP {} COMPLETE", self, i + 1);
-                clear_screen();
-                thread::sleep(seconds);
+            // Draw the grid as you go
+            if let Some(animation) = animation.as_deref_mut() {
+                animation.draw(&mut Text(self.to_string()));
             }
-            if flashes_this_cycle == self.w * self.h {
-                println!("SYNCHRONIZATION COMPLETE:\n{}STEP: {}", self, i + 1);
-                break;
+        }
+        // Linger on the grid at end of step
+        if let Some(animation) = animation {
+            let frame = format!("{}\nSTEP {} COMPLETE", self, i + 1);
+            animation.hold(&mut Text(frame), STEP_PAUSE);
+        }
+        flashes_this_cycle
+    }
+
+    fn show_initial(&self, animation: Option<&mut Animation>) {
+        if let Some(animation) = animation {
+            let frame = format!("INITIAL GRID \n{}", self);
+            animation.hold(&mut Text(frame), INITIAL_PAUSE);
+        }
+    }
+
+    /// For number of `cycles` (steps), charge & flash Octopuses, counting total number of flashes.
+    pub fn count_flashes(&mut self, cycles: usize, mut animation: Option<&mut Animation>) -> usize {
+        self.show_initial(animation.as_deref_mut());
+        (0..cycles)
+            .map(|i| self.step(i, animation.as_deref_mut()))
+            .sum()
+    }
+
+    /// Step until all octopuses flash in one cycle (synchronized), returning which
+    /// step number that occured.
+    pub fn synchronize(&mut self, mut animation: Option<&mut Animation>) -> usize {
+        self.show_initial(animation.as_deref_mut());
+        let octopuses = self.energy.width() * self.energy.height();
+        let mut i = 0;
+        loop {
+            let flashes_this_cycle = self.step(i, animation.as_deref_mut());
+            i += 1;
+            if flashes_this_cycle == octopuses {
+                if let Some(animation) = animation {
+                    let frame = format!("SYNCHRONIZATION COMPLETE:\n{}STEP: {}", self, i);
+                    animation.draw(&mut Text(frame));
+                }
+                return i;
             }
         }
-        total_flashes
     }
 }
 
-pub fn run(example: bool) {
-    let input = if example == true {
-        EXAMPLE_INPUT
-    } else {
-        PUZZLE_INPUT
-    };
+/// Run `solve` on a copy of `octopuses`, animated if `config` says so. The final grid stays on
+/// screen afterwards.
+fn animated<T>(
+    config: &RunConfig,
+    octopuses: &Octopuses,
+    solve: impl FnOnce(&mut Octopuses, Option<&mut Animation>) -> T,
+) -> T {
+    let mut octopuses = octopuses.clone();
+    if !config.animate {
+        return solve(&mut octopuses, None);
+    }
+    let mut animation = Animation::new(config.playback(FPS));
+    let answer = solve(&mut octopuses, Some(&mut animation));
+    animation.finish(&mut Text(octopuses.to_string()));
+    answer
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Octopuses;
 
-    let grid = Grid::from(input.to_string());
-    println!("INITIAL GRID \n{}", grid);
-    let seconds = time::Duration::from_millis(1500);
-    thread::sleep(seconds);
-    clear_screen();
+    fn parse(input: &str) -> Result<Self::Input> {
+        Octopuses::parse(input)
+    }
 
-    let mut part1 = grid.clone();
-    let mut total_flashes = part1.count_flashes(100);
-    println!(
-        "Part 1:\n\nFINAL GRID\n{}total flashes: {}\n",
-        part1, total_flashes
-    );
+    /// Total flashes after 100 steps.
+    fn part1(grid: &Self::Input, config: &RunConfig) -> Result<Answer> {
+        Ok(animated(config, grid, |grid, animation| {
+            grid.count_flashes(100, animation)
+        })
+        .into())
+    }
+
+    /// First step during which all octopuses flash.
+    fn part2(grid: &Self::Input, config: &RunConfig) -> Result<Answer> {
+        Ok(animated(config, grid, |grid, animation| grid.synchronize(animation)).into())
+    }
+}
+
+#[cfg(test)]
+const PUZZLE_INPUT: &str = "\
+6617113584
+6544218638
+5457331488
+1135675587
+1221353216
+1811124378
+1387864368
+4427637262
+6778645486
+3682146745
+";
+
+#[cfg(test)]
+const EXAMPLE_INPUT: &str = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526
+";
+
+#[test]
+fn test_example() {
+    let grid = Octopuses::parse(EXAMPLE_INPUT).unwrap();
+    assert_eq!(grid.clone().count_flashes(100, None), 1656);
+    assert_eq!(grid.clone().synchronize(None), 195);
+}
 
-    let mut part2 = grid.clone();
-    println!("Part 2:");
-    total_flashes = part2.count_flashes(1000);
-    println!("total flashes: {}", total_flashes);
+#[test]
+fn test_puzzle_input() {
+    let grid = Octopuses::parse(PUZZLE_INPUT).unwrap();
+    assert_eq!(grid.clone().count_flashes(100, None), 1599);
+    assert_eq!(grid.clone().synchronize(None), 418);
 }
@@ -1,7 +1,9 @@
 /// Copied from https://github.com/McSick/AdventOfCode2021/blob/main/12/tree-pathfind/src/main.rs
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::{env, fs};
+
+use crate::error::{lines, AocError, Result};
+use crate::solution::{Answer, RunConfig, Solution};
 
 const MAX_VTEX: usize = 13;
 
@@ -39,22 +41,19 @@ impl Graph {
             for i in row {
                 print!("{: >2} ", i);
             }
-            print!("|\n");
+            println!("|");
         }
     }
 
     fn add_vertex(&mut self, vtex: &str) {
-        if !self.vertex_map.contains_key(&vtex.to_string()) {
+        if !self.vertex_map.contains_key(vtex) {
             self.vertex_map.insert(vtex.to_string(), self.vindex);
             self.vindex += 1;
         }
     }
 
-    fn get_vertex(&self, vtex: &str) -> usize {
-        match self.vertex_map.get(&vtex.to_string()) {
-            Some(vindex) => *vindex,
-            None => panic!("No vertex found for '{}'", vtex),
-        }
+    pub fn get_vertex(&self, vtex: &str) -> Option<usize> {
+        self.vertex_map.get(vtex).copied()
     }
 
     // fn get_vertex_str(&self, vtex: usize) -> &str {
@@ -62,8 +61,8 @@ impl Graph {
     // }
 
     fn add_edge(&mut self, from: &str, to: &str) {
-        let from_idx = self.get_vertex(from);
-        let to_idx = self.get_vertex(to);
+        let from_idx = self.vertex_map[from];
+        let to_idx = self.vertex_map[to];
         let mut from_val = 1;
         let mut to_val = 1;
         // Identify edges to/from a "small cave" in the adjency matrix
@@ -93,17 +92,18 @@ impl Graph {
     /// keeping count of final paths.
     ///
     /// Use
-    fn traverse(&mut self, from: usize, mut visited: HashSet<usize>, hit_twice: bool) -> i32 {
+    pub fn traverse(&self, from: usize, mut visited: HashSet<usize>, hit_twice: bool) -> i32 {
         visited.insert(from);
         let paths = self.adj_matrix[from];
+        let (start, end) = (self.get_vertex("start"), self.get_vertex("end"));
         let mut count = 0;
         for to in 0..paths.len() {
             let edge = self.get_edge(from, to);
 
             if edge == 1 || edge == -1 {
-                if self.get_vertex("start") == to {
+                if start == Some(to) {
                     continue;
-                } else if self.get_vertex("end") == to {
+                } else if end == Some(to) {
                     // for v in &visited {
                     //     print!("{}-", self.get_vertex_str(*v));
                     // }
@@ -120,42 +120,66 @@ impl Graph {
     }
 }
 
-impl From<String> for Graph {
-    fn from(string: String) -> Self {
+impl TryFrom<&str> for Graph {
+    type Error = AocError;
+
+    fn try_from(string: &str) -> Result<Self> {
         let mut graph = Graph::new();
-        for line in string.lines() {
-            let split = line.split('-').collect::<Vec<&str>>();
-            let from = split[0];
-            let to = split[1];
-            graph.add_vertex(from);
-            graph.add_vertex(to);
+        for line in lines(12, string) {
+            let (from, to) = line
+                .text
+                .split_once('-')
+                .ok_or_else(|| line.error_at(1, "expected a connection like 'start-A'"))?;
+            for cave in [from, to] {
+                if cave.is_empty() {
+                    return Err(line.error(cave, "empty cave name"));
+                }
+                graph.add_vertex(cave);
+                if graph.vindex > MAX_VTEX {
+                    return Err(line.error(cave, format!("more than {} caves", MAX_VTEX)));
+                }
+            }
             graph.add_edge(from, to);
         }
         graph.str_vx_map = swap_hashmap(&graph.vertex_map);
-        graph
+        Ok(graph)
     }
 }
 
-pub fn run(example: bool) {
-    let path = if example {
-        "inputs/day12_example.txt"
-    } else {
-        "inputs/day12.txt"
-    };
-
-    // Part 1
-    let mut graph = Graph::from(
-        fs::read_to_string(path).unwrap_or_else(|e| panic!("error opening file: {}", e)),
-    );
-    if env::var("DEBUG").unwrap_or("false".to_string()) == "true" {
+/// The cave every path starts from.
+fn start_cave(graph: &Graph) -> Result<usize> {
+    graph
+        .get_vertex("start")
+        .ok_or_else(|| AocError::solve(12, "there is no 'start' cave"))
+}
+
+/// Number of paths from the start cave to the end, printing the graph first when debugging.
+fn count_paths(graph: &Graph, config: &RunConfig, hit_twice: bool) -> Result<i32> {
+    if config.debug() {
         graph.print();
     }
-    let result = graph.traverse(graph.get_vertex("start"), HashSet::new(), true);
-    println!("Part 1: {}", result);
+    Ok(graph.traverse(start_cave(graph)?, HashSet::new(), hit_twice))
+}
 
-    // Part 2
-    let result = graph.traverse(graph.get_vertex("start"), HashSet::new(), false);
-    println!("Part 2: {}", result);
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Graph::try_from(input)
+    }
+
+    /// Paths that visit small caves at most once.
+    fn part1(graph: &Self::Input, config: &RunConfig) -> Result<Answer> {
+        Ok(count_paths(graph, config, true)?.into())
+    }
+
+    /// Paths that may visit a single small cave twice.
+    fn part2(graph: &Self::Input, config: &RunConfig) -> Result<Answer> {
+        Ok(count_paths(graph, config, false)?.into())
+    }
 }
 
 #[test]
@@ -168,10 +192,10 @@ b-d
 A-end
 b-end
 "#;
-    let mut graph = Graph::from(test_string.to_string());
+    let graph = Graph::try_from(test_string).unwrap();
     println!("Map: {:?}", graph);
     graph.print();
-    let result = graph.traverse(graph.get_vertex("start"), HashSet::new(), true);
+    let result = graph.traverse(graph.get_vertex("start").unwrap(), HashSet::new(), true);
     assert!(result == 10);
 }
 
@@ -188,8 +212,8 @@ kj-sa
 kj-HN
 kj-dc
 "#;
-    let graph = Graph::from(test_string.to_string());
-    println!("Map: {:?}", graph);
-    // graph.print();
-    assert!(true);
+    let graph = Graph::try_from(test_string).unwrap();
+    let start = graph.get_vertex("start").unwrap();
+    assert_eq!(graph.traverse(start, HashSet::new(), true), 19);
+    assert_eq!(graph.traverse(start, HashSet::new(), false), 103);
 }
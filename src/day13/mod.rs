@@ -1,11 +1,19 @@
-use std::{collections::HashSet, fs};
+use std::collections::HashSet;
+
+use crate::animation::{Animation, Text};
+use crate::error::{lines, AocError, Result};
+use crate::grid::Grid;
+use crate::solution::{Answer, RunConfig, Solution};
+
+/// Folds per second when animating: each one stays on screen for 800ms.
+const FPS: f64 = 1.25;
 
 // So easy to define type aliases in rust!
-type Point = (isize, isize);
+pub type Point = (isize, isize);
 
 /// Take the point defining a fold line & the number of currently visible points
 /// and return the points visible after executing the fold
-fn fold(fold_pt: Point, points: &Vec<Point>) -> Vec<Point> {
+pub fn fold(fold_pt: Point, points: &[Point]) -> Vec<Point> {
     let mut keep: HashSet<Point> = HashSet::new();
     let mut to_fold: Vec<Point> = Vec::new();
     // Keep any points on the "upper" side of the paper for a fold along the x-axis.
@@ -37,7 +45,7 @@ fn fold(fold_pt: Point, points: &Vec<Point>) -> Vec<Point> {
 ///
 /// For example:
 /// If the point is (2, 8) and the fold line is (0, 7), then the result is
-/// ```
+/// ```text
 /// -> (|(x1 - x2*2)|, |(y1 - y2*2)|)
 /// -> (|(2 - 0*2)|, |(8 - 7*2)|)
 /// -> (|2 - 0|, |8 - 14|)
@@ -48,81 +56,228 @@ fn get_new_point(pt: Point, fold_pt: Point) -> Point {
     ((pt.0 - fold_pt.0 * 2).abs(), (pt.1 - fold_pt.1 * 2).abs())
 }
 
-/// Get the coordinate for the lower right-hand corner of the grid
-fn get_max_xy(pts: &Vec<Point>) -> Point {
-    let max_x = pts.iter().map(|pt| pt.0).max().unwrap();
-    let max_y = pts.iter().map(|pt| pt.1).max().unwrap();
-    (max_x, max_y)
-}
-
 /// Read puzzle input into a vector of two  vectors of coordinate points:
-/// visible dots, and fold lines
-fn parse_input(path: &str) -> (Vec<Point>, Vec<Point>) {
+/// visible dots, and fold lines (the two sections are separated by a blank line)
+pub fn parse_input(string: &str) -> Result<(Vec<Point>, Vec<Point>)> {
     let mut points: Vec<Point> = vec![];
     let mut folds = vec![];
-    let instructions = fs::read_to_string(path)
-        .unwrap_or_else(|e| panic!("couldn't open file: {}", e))
-        .split("\n\n")
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
-    for line in instructions[0].lines() {
-        let split = line
-            .split(',')
-            .map(|i| isize::from_str_radix(i, 10).unwrap())
-            .collect::<Vec<isize>>();
-        points.push((split[0], split[1]));
-    }
-    for line in instructions[1].lines() {
-        // a fold line is represented by a single x, y coordinate
-        // y=7 -> (0, 7), x=5 -> (5, 0)
-        let split: Vec<&str> = line.split(' ').collect();
-        let fold: Vec<&str> = split[2].split('=').collect();
-        match fold[0] {
-            "x" => folds.push((isize::from_str_radix(fold[1], 10).unwrap(), 0)),
-            "y" => folds.push((0, isize::from_str_radix(fold[1], 10).unwrap())),
-            _ => {
-                panic!("WUT")
+    let mut in_folds = false;
+    for line in lines(13, string) {
+        // the paper's top left corner is 0,0: negative coordinates are off the paper
+        let coordinate = |token: &str| -> Result<isize> {
+            match line.parse(token)? {
+                n if n < 0 => Err(line.error(token, "coordinates can't be negative")),
+                n => Ok(n),
+            }
+        };
+        if line.text.is_empty() {
+            if in_folds {
+                return Err(line.error_at(1, "unexpected blank line"));
+            }
+            in_folds = true;
+        } else if !in_folds {
+            let (x, y) = line
+                .text
+                .split_once(',')
+                .ok_or_else(|| line.error_at(1, "expected a dot like '6,10'"))?;
+            points.push((coordinate(x)?, coordinate(y)?));
+        } else {
+            // a fold line is represented by a single x, y coordinate
+            // y=7 -> (0, 7), x=5 -> (5, 0)
+            let (axis, value) = line
+                .text
+                .strip_prefix("fold along ")
+                .and_then(|fold| fold.split_once('='))
+                .ok_or_else(|| line.error_at(1, "expected a fold like 'fold along y=7'"))?;
+            match axis {
+                "x" => folds.push((coordinate(value)?, 0)),
+                "y" => folds.push((0, coordinate(value)?)),
+                _ => return Err(line.error(axis, "fold axis must be 'x' or 'y'")),
             }
         }
     }
-    (points, folds)
+    Ok((points, folds))
 }
 
-/// Display a grid using '.' for empty spaces and '#' for visible points
+/// Largest paper (in positions) we're willing to draw: the puzzle inputs start out at about a
+/// million, and only get smaller as they're folded.
+const MAX_PAPER_SIZE: usize = 1 << 24;
+
+/// Mark the visible points on a grid just big enough to hold them all.
+fn paper(pts: &[Point]) -> Result<Grid<bool>> {
+    let width = pts.iter().map(|pt| pt.0 + 1).max().unwrap_or(0) as usize;
+    let height = pts.iter().map(|pt| pt.1 + 1).max().unwrap_or(0) as usize;
+    if width
+        .checked_mul(height)
+        .is_none_or(|size| size > MAX_PAPER_SIZE)
+    {
+        return Err(AocError::solve(
+            13,
+            format!("paper too big to draw ({}x{})", width, height),
+        ));
+    }
+    let mut paper = Grid::from_fn(width, height, |_| false);
+    for &(x, y) in pts {
+        paper[(x as usize, y as usize)] = true;
+    }
+    Ok(paper)
+}
+
+/// Render a grid using '.' for empty spaces and '#' for visible points
 /// from an input vector of (x, y) coordinates.
-fn print_pt_grid(pts: Vec<Point>) {
-    let max_xy = get_max_xy(&pts);
-    for y in 0..=max_xy.1 {
-        for x in 0..=max_xy.0 {
-            if pts.contains(&(x, y)) {
-                print!("#")
-            } else {
-                print!(".")
+pub fn render_pt_grid(pts: &[Point]) -> Result<String> {
+    Ok(paper(pts)?
+        .map(|&dot| if dot { '#' } else { '.' })
+        .to_string())
+}
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+/// The 4x6 capital letters AoC draws its codes with, as `#`/`.` rows from top to bottom.
+/// Letters are laid out 5 columns apart (one blank column between them).
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Read the letters spelled out by the visible points.
+pub fn ocr(pts: &[Point]) -> Result<String> {
+    let paper = paper(pts)?;
+    if paper.height() != GLYPH_HEIGHT {
+        return Err(AocError::solve(
+            13,
+            format!(
+                "expected letters {} dots tall, found {}",
+                GLYPH_HEIGHT,
+                paper.height()
+            ),
+        ));
+    }
+    (0..paper.width())
+        .step_by(GLYPH_WIDTH + 1)
+        .map(|x| {
+            // the last letter's right-hand column may be blank, so reading past the edge is a '.'
+            let dot = |pos| paper.get(pos).copied().unwrap_or(false);
+            let matches = |font_glyph: &[&str; GLYPH_HEIGHT]| {
+                font_glyph.iter().enumerate().all(|(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .all(|(dx, c)| (c == '#') == dot((x + dx, y)))
+                })
+            };
+            FONT.iter()
+                .find(|(_, font_glyph)| matches(font_glyph))
+                .map(|(letter, _)| *letter)
+                .ok_or_else(|| {
+                    AocError::solve(13, format!("unrecognized letter at column {}", x + 1))
+                })
+        })
+        .collect()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = (Vec<Point>, Vec<Point>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    /// Number of visible points after a single fold.
+    fn part1((points, folds): &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        let first = folds
+            .first()
+            .ok_or_else(|| AocError::solve(13, "no fold instructions"))?;
+        Ok(fold(*first, points).len().into())
+    }
+
+    /// Letters spelled out by the folded paper. Dots that don't spell anything (like the example's
+    /// square) are rendered as `#`/`.` art instead.
+    fn part2((points, folds): &Self::Input, config: &RunConfig) -> Result<Answer> {
+        let mut animation = config.animate.then(|| Animation::new(config.playback(FPS)));
+        let mut points = points.clone();
+        for (i, f) in folds.iter().enumerate() {
+            points = fold(*f, &points);
+            if let Some(animation) = &mut animation {
+                let paper = render_pt_grid(&points)?;
+                let frame = format!("FOLD {} OF {}\n{}", i + 1, folds.len(), paper);
+                animation.draw(&mut Text(frame));
             }
         }
-        println!()
+        if let Some(animation) = &mut animation {
+            animation.finish(&mut Text(render_pt_grid(&points)?));
+        }
+        match ocr(&points) {
+            Ok(letters) => Ok(letters.into()),
+            Err(_) => Ok(render_pt_grid(&points)?.into()),
+        }
     }
 }
 
-pub fn run(example: bool) {
-    let path = if example {
-        "inputs/day13_example.txt"
-    } else {
-        "inputs/day13.txt"
-    };
-    let (mut points, folds) = parse_input(path);
-
-    // Part 1
-    points = fold(*folds.first().unwrap(), &points);
-    println!(
-        "Part 1 - number of visible points after single fold: {}\n",
-        points.len()
-    );
-
-    // Part 2
-    for f in folds {
-        points = fold(f, &points);
-    }
-    println!("Part 2 - result of folds:");
-    print_pt_grid(points);
+/// Points of the `#`s in `art`.
+#[cfg(test)]
+fn dots(art: &str) -> Vec<Point> {
+    art.lines()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.char_indices()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (x as isize, y as isize))
+        })
+        .collect()
+}
+
+#[test]
+fn test_ocr() {
+    let art = "\
+###...##..###..#.....##..#..#.#..#.#...
+#..#.#..#.#..#.#....#..#.#.#..#..#.#...
+#..#.#....#..#.#....#..#.##...####.#...
+###..#....###..#....####.#.#..#..#.#...
+#.#..#..#.#....#....#..#.#.#..#..#.#...
+#..#..##..#....####.#..#.#..#.#..#.####";
+    assert_eq!(ocr(&dots(art)).unwrap(), "RCPLAKHL");
+    let art = "\
+.###.#..#
+..#..#..#
+..#..####
+..#..#..#
+..#..#..#
+.###.#..#";
+    assert_eq!(ocr(&dots(art)).unwrap(), "IH");
+    // the example folds into a square, which isn't a letter
+    let square: Vec<Point> = (0..5)
+        .flat_map(|i| [(i, 0), (i, 4), (0, i), (4, i)])
+        .collect();
+    assert!(ocr(&square).is_err());
+}
+
+#[test]
+fn test_coordinate_bounds() {
+    assert!(parse_input("6,10\n\nfold along y=7\n").is_ok());
+    assert!(parse_input("-1,0\n\nfold along y=7\n").is_err());
+    assert!(parse_input("6,10\n\nfold along x=-5\n").is_err());
+    // far away dots are fine to count, but not to draw
+    let far = parse_input("1000000000,1000000000\n\nfold along x=1\n").unwrap();
+    let config = RunConfig::default();
+    assert_eq!(Day13::part1(&far, &config).unwrap(), 1usize.into());
+    assert!(Day13::part2(&far, &config).is_err());
 }
@@ -1,150 +1,190 @@
-use std::collections::{BTreeMap, HashMap};
-use std::env::var;
-use std::fs::read_to_string;
-use std::hash::Hash;
-
-type RuleMap = BTreeMap<String, char>;
-type CharArr = [isize; 26];
-type Counter = BTreeMap<char, isize>;
-
-// fn char_to_id(ch: char) -> usize {
-//     let char_id = ch as usize - b'A' as usize;
-//     println!("char id for {}: {}", ch, char_id);
-//     char_id
-// }
-
-fn counter_from_str<S: Into<String>>(s: S) -> Counter {
-    let mut counter = BTreeMap::new();
-    s.into().chars().for_each(|ch| {
-        *counter.entry(ch).or_insert(0) += 1;
-    });
-    counter
+/// Pair-count approach borrowed from a solution on the AoC subreddit -- a memoized recursion over
+/// whole polymer strings never finished for 40 steps.
+///
+/// The order of the pairs doesn't matter, only how many of each there are: every step, each pair
+/// `AB` with a rule `AB -> C` becomes one `AC` and one `CB`. Since neighbouring pairs overlap, every
+/// element is counted once as the right half of a pair -- except the very first element of the
+/// template, which never changes.
+use crate::error::{lines, AocError, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
+
+const ELEMENT_COUNT: usize = 26; // Map 'A'..'Z' to id's 0 to 25
+const PAIR_COUNT: usize = ELEMENT_COUNT * ELEMENT_COUNT;
+
+type PairCounts = [usize; PAIR_COUNT];
+type ElementCounts = [usize; ELEMENT_COUNT];
+
+/// Each pair is either not expanded (no rule provided), or expands into two new pairs
+/// (the new element pairs with each element of the original pair).
+#[derive(Debug, Clone, Copy)]
+enum Rule {
+    NoExpansion,
+    TwoPairs(usize, usize),
 }
 
-fn char_windows<'a>(src: &'a str, win_size: usize) -> impl Iterator<Item = &'a str> {
-    src.char_indices().flat_map(move |(from, _)| {
-        src[from..]
-            .char_indices()
-            .skip(win_size - 1)
-            .next()
-            .map(|(to, c)| &src[from..from + to + c.len_utf8()])
-    })
+fn pair_to_index(left: usize, right: usize) -> usize {
+    ELEMENT_COUNT * left + right
+}
+
+/// Parse the elements (`A`-`Z`) of `token`, a sub-slice of `line`.
+fn elements(line: &InputLine, token: &str) -> Result<Vec<usize>> {
+    let start = line.column_of(token);
+    token
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            'A'..='Z' => Ok(c as usize - 'A' as usize),
+            _ => Err(line.error_at(start + i, format!("invalid element '{}'", c))),
+        })
+        .collect()
+}
+
+/// Polymer template (as pair counts) along with the pair insertion rules.
+#[derive(Debug, Clone)]
+pub struct Polymer {
+    first_element: usize,
+    pair_counts: PairCounts,
+    rules: [Rule; PAIR_COUNT],
 }
 
-fn memoize<A, R, F>(
-    cache: &mut HashMap<(A, usize), R>,
-    func: F,
-    arg: A,
-    rules: &RuleMap,
-    steps: usize,
-) -> R
-where
-    A: Eq + Hash + Clone,
-    R: Clone,
-    F: Fn(&mut HashMap<(A, usize), R>, A, &RuleMap, usize) -> R,
-{
-    match cache.get(&(arg.clone(), steps)).map(|x| x.clone()) {
-        Some(result) => result,
-        None => {
-            let result = func(cache, arg.clone(), rules, steps);
-            cache.insert((arg, steps), result.clone());
-            result
+/// Parse the polymer template, a blank line, then pair insertion rules like `CH -> B`.
+pub fn parse_input(string: &str) -> Result<Polymer> {
+    let mut input = lines(14, string);
+    let template_line = input
+        .next()
+        .filter(|line| !line.text.is_empty())
+        .ok_or_else(|| InputLine::new(14, 1, "").error_at(1, "missing polymer template"))?;
+    let template = elements(&template_line, template_line.text)?;
+
+    let mut pair_counts = [0; PAIR_COUNT];
+    for elems in template.windows(2) {
+        pair_counts[pair_to_index(elems[0], elems[1])] += 1;
+    }
+
+    // By default, a pair is not expanded unless a rule has been provided...
+    let mut rules = [Rule::NoExpansion; PAIR_COUNT];
+    for line in input {
+        if line.number == 2 {
+            if !line.text.is_empty() {
+                return Err(line.error_at(1, "expected a blank line after the template"));
+            }
+            continue;
         }
+        let bad_rule = || line.error_at(1, "expected a rule like 'CH -> B'");
+        let (pair, output) = line.text.split_once(" -> ").ok_or_else(bad_rule)?;
+        let (left, right, output) =
+            match (&elements(&line, pair)?[..], &elements(&line, output)?[..]) {
+                (&[left, right], &[output]) => (left, right, output),
+                _ => return Err(bad_rule()),
+            };
+        rules[pair_to_index(left, right)] =
+            Rule::TwoPairs(pair_to_index(left, output), pair_to_index(output, right));
     }
+
+    Ok(Polymer {
+        first_element: template[0],
+        pair_counts,
+        rules,
+    })
 }
 
-fn counter_diff(a: Counter, b: Counter) -> Counter {
-    let mut counter: Counter = BTreeMap::new();
-    for (k, v) in a.iter() {
-        counter.insert(*k, *v);
-        let b_val = b.get(&k).unwrap_or(&0_isize);
-        *counter.entry(*k).or_insert(0) -= b_val;
+impl Polymer {
+    /// Apply the pair insertion rules `steps` times. The polymer roughly doubles in length every
+    /// step, so after 60 or so steps it's too long to count.
+    pub fn step(&self, steps: usize) -> Result<Polymer> {
+        let too_long = || {
+            AocError::solve(
+                14,
+                format!("polymer too long to count after {} steps", steps),
+            )
+        };
+        let mut pair_counts = self.pair_counts;
+        for _ in 0..steps {
+            let mut next = [0usize; PAIR_COUNT];
+            for (pair_index, &count) in pair_counts.iter().enumerate() {
+                let new_pairs = match self.rules[pair_index] {
+                    Rule::NoExpansion => [Some(pair_index), None],
+                    Rule::TwoPairs(pair1_index, pair2_index) => {
+                        [Some(pair1_index), Some(pair2_index)]
+                    }
+                };
+                for new_pair in new_pairs.into_iter().flatten() {
+                    next[new_pair] = next[new_pair].checked_add(count).ok_or_else(too_long)?;
+                }
+            }
+            // no rule applies any more: every further step is the same
+            if next == pair_counts {
+                break;
+            }
+            pair_counts = next;
+        }
+        Ok(Polymer {
+            pair_counts,
+            ..self.clone()
+        })
     }
-    println!("counter before retain{:?}", counter);
-    counter.retain(|_, v| *v > 0);
-    println!("DIFF_RES {:?}", counter);
-    counter
-}
 
-fn counter_sum(a: Counter, b: Counter) -> Counter {
-    let mut counter: Counter = BTreeMap::new();
-    println!("SUMMING a: {:?}, b: {:?}", a, b);
-    for (k, v) in a.iter() {
-        counter.insert(*k, *v);
-        let b_val = b.get(&k).unwrap_or(&0_isize);
-        *counter.entry(*k).or_insert(0) += v + b_val;
+    /// Number of each element (`'A'..='Z'`) in the polymer, or `None` if there are too many to
+    /// count.
+    fn element_counts(&self) -> Option<ElementCounts> {
+        let mut counts = [0usize; ELEMENT_COUNT];
+        // extract and count the right element of each pair
+        for (pair_index, &count) in self.pair_counts.iter().enumerate() {
+            let element = pair_index % ELEMENT_COUNT;
+            counts[element] = counts[element].checked_add(count)?;
+        }
+        // Don't forget to count the initial element in the final string
+        counts[self.first_element] = counts[self.first_element].checked_add(1)?;
+        Some(counts)
+    }
+
+    /// Quantity of the most common element minus quantity of the least common element.
+    pub fn max_min_difference(&self) -> Result<usize> {
+        let counts = self
+            .element_counts()
+            .ok_or_else(|| AocError::solve(14, "too many elements to count"))?;
+        let present = counts.iter().filter(|&&count| count > 0);
+        let max = present.clone().max().unwrap_or(&0);
+        let min = present.min().unwrap_or(&0);
+        Ok(max - min)
     }
-    println!("SUM RES: {:?}", counter);
-    counter
 }
 
-fn parse_input(filename: &str) -> (String, RuleMap, Counter) {
-    let mut rule_map: RuleMap = BTreeMap::new();
-    let path = format!(
-        "{}/day14/src/{}",
-        var("AOC_DIR").unwrap_or_else(|e| panic!("Env var error 'AOC_DIR': {}", e)),
-        filename
-    );
-    let string =
-        read_to_string(path.as_str()).unwrap_or_else(|e| panic!("error opening file: {}", e));
-    let (template, rules) = string.split_once("\n\n").unwrap();
-    rules.lines().into_iter().for_each(|line| {
-        let (pair, ch) = line.split_once(" -> ").unwrap();
-        rule_map.insert(pair.to_owned(), ch.chars().next().unwrap());
-    });
-    (template.to_string(), rule_map, counter_from_str(template))
+/// Most common minus least common element after any number of pair insertion `steps`.
+pub fn solve(polymer: &Polymer, steps: usize) -> Result<usize> {
+    polymer.step(steps)?.max_min_difference()
 }
 
-fn count(
-    cache: &mut HashMap<(String, usize), Counter>,
-    poly: String,
-    rules: &RuleMap,
-    steps: usize,
-) -> Counter {
-    if steps == 0 {
-        return counter_from_str(poly);
-    };
-
-    if poly.len() > 2 {
-        let vec = poly.chars().map(|c| c.to_string()).collect::<Vec<String>>();
-        let middle = &vec[1..vec.len() - 1].join("");
-        for pair in char_windows(poly.as_str(), 2) {
-            let hit = match cache.get(&(pair.to_string(), steps)) {
-                Some(result) => result.clone(),
-                None => memoize(cache, count, pair.to_string(), rules, steps - 1)
-            };
-            return counter_diff(
-                counter_sum(
-                    hit,
-                    BTreeMap::new(),
-                ),
-                counter_from_str(middle),
-            );
-        }
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Polymer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    /// Most common minus least common element after 10 steps.
+    fn part1(polymer: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(solve(polymer, 10)?.into())
+    }
+
+    /// Most common minus least common element after 40 steps.
+    fn part2(polymer: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(solve(polymer, 40)?.into())
     }
-    let new_char = rules.get(poly.as_str()).unwrap().to_string();
-    let p1 = format!("{}{}", poly.chars().next().unwrap(), &new_char);
-    println!("p1: {}", p1);
-    let p2 = format!("{}{}", poly.chars().last().unwrap(), &new_char);
-    println!("p2: {}", p2);
-    let result = counter_diff(
-        counter_sum(
-            memoize(cache, count, p1, rules, steps-1),
-            memoize(cache, count, p2, rules, steps-1),
-        ), counter_from_str(new_char.to_string()));
-    cache.insert((poly, steps-1), result.clone());
-    println!("SUM DIFF: {:?}", result);
-    result
 }
 
-fn main() {
-    let (template, rules, initial_counter) = parse_input("input_example.txt");
-    println!(
-        "template: {}\nrule map: {:?}\nchar arr counts: {:?}",
-        template, rules, initial_counter
-    );
-    let mut cache = HashMap::new();
-    let result = count(&mut cache, template, &rules, 6);
-    println!("result: {:?}", result);
-    println!(": {:?}", cache);
+#[test]
+fn test_example() {
+    let polymer = parse_input(include_str!("../../inputs/day14_example.txt")).unwrap();
+    // NNCB -> NCNBCHB: two each of B, C and N, one H
+    assert_eq!(solve(&polymer, 1).unwrap(), 1);
+    assert_eq!(solve(&polymer, 10).unwrap(), 1588);
+    assert_eq!(solve(&polymer, 40).unwrap(), 2188189693529);
+    assert!(solve(&polymer, 100).is_err());
+    // without rules the polymer never changes, however many steps it's given
+    let template = parse_input("NNCB\n").unwrap();
+    assert_eq!(solve(&template, usize::MAX).unwrap(), 1);
 }
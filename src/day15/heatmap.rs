@@ -0,0 +1,82 @@
+//! Export a finished `RiskSearch` as an image, for maps too big to watch in a terminal.
+//!
+//! Every position is shaded by the total risk of reaching it (darker is safer), with the least
+//! risky path drawn on top in red. Positions the search never reached are left black. Images are
+//! binary PPMs (`P6`), which most image viewers open and which need no image library to write.
+use std::fs;
+use std::path::Path;
+
+use super::{RiskSearch, WeightedGraph, Wrap};
+use crate::error::{AocError, Result};
+use crate::grid::{Grid, Pos};
+
+const PATH_COLOR: [u8; 3] = [255, 0, 0];
+/// Reached positions are shaded from this up to white, so they stand out from unreached ones.
+const MIN_SHADE: u32 = 48;
+
+/// PPM image of `search`'s costs, with `path` overlaid.
+pub fn heatmap(graph: &WeightedGraph, search: &RiskSearch, path: &[Pos]) -> Vec<u8> {
+    let weights = &graph.weights;
+    let risks = Grid::from_fn(weights.width(), weights.height(), |pos| search.risk(pos));
+    let max_risk = risks.cells().flatten().copied().max().unwrap_or(0).max(1);
+    let mut pixels = risks.map(|risk| match risk {
+        Some(risk) => {
+            let shade = MIN_SHADE + risk * (255 - MIN_SHADE) / max_risk;
+            [shade as u8; 3]
+        }
+        None => [0; 3],
+    });
+    for &pos in path {
+        pixels[pos] = PATH_COLOR;
+    }
+
+    let mut image = format!("P6\n{} {}\n255\n", weights.width(), weights.height()).into_bytes();
+    image.extend(pixels.cells().flatten());
+    image
+}
+
+/// Search the map for `part` (the tiled map for part 2) and write the resulting heatmap to `file`.
+pub fn write_heatmap(graph: &WeightedGraph, part: u8, file: &Path) -> Result<()> {
+    let tiled;
+    let graph = if part == 2 {
+        tiled = graph.tiled(5, 5, Wrap::default());
+        &tiled
+    } else {
+        graph
+    };
+    let goal = graph.bottom_right();
+    let mut search = RiskSearch::new(graph, (0, 0), goal, true);
+    search.find(|&pos| pos == goal);
+    let path = search
+        .path_to(goal)
+        .ok_or_else(|| AocError::solve(15, "no path to the bottom right corner"))?;
+    fs::write(file, heatmap(graph, &search, &path)).map_err(|source| AocError::Output {
+        path: file.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day15::parse_input;
+
+    #[test]
+    fn test_heatmap() {
+        let graph = parse_input("19\n11\n").unwrap();
+        let goal = graph.bottom_right();
+        let mut search = RiskSearch::new(&graph, (0, 0), goal, false);
+        search.find(|&pos| pos == goal);
+        let path = search.path_to(goal).unwrap();
+        assert_eq!(path, [(0, 0), (0, 1), (1, 1)]);
+
+        let image = heatmap(&graph, &search, &path);
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        // (1, 0) costs 9 to reach, the most of any position: white
+        assert_eq!(
+            &image[header.len()..],
+            [255, 0, 0, 255, 255, 255, 255, 0, 0, 255, 0, 0]
+        );
+    }
+}
@@ -1,209 +1,216 @@
-use priority_queue::DoublePriorityQueue;
-use std::collections::HashMap;
-use std::env;
-use std::fs::read_to_string;
-use std::{thread, time};
-
-use lazy_static::lazy_static;
-
-lazy_static! {
-    static ref DEBUG: bool = if env::var("DEBUG").unwrap_or("false".to_string()) == "true" {
-        true
-    } else {
-        false
-    };
-}
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
-const SLEEP: time::Duration = time::Duration::from_millis(100);
+use crate::animation::Animation;
+use crate::error::{AocError, Result};
+use crate::grid::{Grid, Pos};
+use crate::search::Graph;
+use crate::solution::{Answer, RunConfig, Solution};
 
-type Coord = (isize, isize);
-type PathMap = HashMap<Coord, Coord>;
-type CostMap = HashMap<Coord, f64>;
+mod heatmap;
+mod render;
 
-///// Allows for printing animated grid state inside each cycle
-fn clear_screen() {
-    print!("{esc}c", esc = 27 as char);
-}
+pub use heatmap::{heatmap, write_heatmap};
+pub use render::PathRenderer;
+
+const FPS: f64 = 10.0;
+/// Roughly how many frames an animated search is drawn in.
+const ANIMATION_FRAMES: usize = 200;
+
+/// Costs are packed into a `u64`: total risk in the high 32 bits, "pretty path" nudges in the low
+/// 32 bits. Adding and comparing packed costs then orders paths by risk first, nudges second.
+const RISK_SHIFT: u32 = 32;
+const UNREACHED: u64 = u64::MAX;
+const NO_PREDECESSOR: usize = usize::MAX;
 
 /// Thank you, [Redblob Games](https://www.redblobgames.com/pathfinding/a-star/implementation.html) for the excellent implementation
 /// explanations for Dijkstra & A* search.
 ///
-/// This implementation is very much like the Python one shown on Redblob games. Uses `priority_queue::DoublePriorityQueue` for popping the
-/// minimum-weighted value on each step. Because of how the values are distributed in this particular graph, _most_ of the nodes
-/// (generally, _ALL_ of them for these puzzle inputs...) are visited in the quest to find the least-cost path through to the
-/// bottom-right corner of the grid.
-fn a_star_search(
-    graph: &WeightedGraph,
-    start: Coord,
-    goal: Coord,
-    animate: bool,
-) -> (PathMap, CostMap) {
-    let mut frontier = DoublePriorityQueue::new();
-    frontier.push(start, 0);
-    let mut came_from: PathMap = HashMap::new();
-    let mut cost_so_far: CostMap = HashMap::new();
-    came_from.insert(start, (-1, -1));
-    cost_so_far.insert(start, 0.0);
-
-    while !frontier.is_empty() {
-        let current = frontier.pop_min().unwrap().0;
-        if animate {
-            let path_so_far = reconstruct_path(&came_from, start, current);
-            graph.display_with_path(&path_so_far, &cost_so_far);
-            thread::sleep(SLEEP);
-        }
-
-        if current == goal {
-            break;
-        }
+/// A* over the risk grid, specialised for speed: costs are integers, and the costs so far and the
+/// path taken to each position live in flat arrays indexed like the grid's cells instead of maps.
+/// Iterating the search settles one position at a time, cheapest first, and yields it. Because of
+/// how the values are distributed in this particular graph, _most_ of the nodes (generally, _ALL_
+/// of them for these puzzle inputs...) are visited in the quest to find the least-cost path through
+/// to the bottom-right corner of the grid.
+pub struct RiskSearch<'g> {
+    graph: &'g WeightedGraph,
+    goal: Pos,
+    /// Break ties between equally risky paths with the nudges described in `WeightedGraph::nudge`.
+    pretty: bool,
+    /// Lowest risk of any position, i.e. the least any step can cost.
+    min_step: u64,
+    /// (cost so far + heuristic, cost so far, cell index)
+    frontier: BinaryHeap<Reverse<(u64, u64, usize)>>,
+    costs: Vec<u64>,
+    came_from: Vec<usize>,
+}
 
-        // Visit all E W N S neighbors of the grid
-        // get the total cost so far to visit this node
-        // if the neighbor's total cost is not in the CostMap
-        // OR it has been visited before, and its new edge cost is less than its previous value
-        // - insert it into the CostMap
-        // - tag it with a priority of its total_cost + cost returned by the heuristic
-        // - put it into the `priority_queue::DoublePriorityQueue`
-        // - add the new k, v pair to the PathMap
-        for next in graph.neighbors(current) {
-            let new_cost = cost_so_far.get(&current).unwrap() + graph.cost(current, next);
-            if !cost_so_far.contains_key(&next) || new_cost < *cost_so_far.get(&next).unwrap() {
-                cost_so_far.insert(next, new_cost);
-                let priority = new_cost + heuristic(next, goal);
-                frontier.push(next, priority as isize);
-                came_from.insert(next, current);
-            }
-        }
-        if animate {
-            clear_screen();
-        }
+impl<'g> RiskSearch<'g> {
+    pub fn new(graph: &'g WeightedGraph, start: Pos, goal: Pos, pretty: bool) -> Self {
+        let cells = graph.weights.width() * graph.weights.height();
+        let mut search = RiskSearch {
+            graph,
+            goal,
+            pretty,
+            min_step: graph.weights.cells().min().copied().unwrap_or(0).into(),
+            frontier: BinaryHeap::new(),
+            costs: vec![UNREACHED; cells],
+            came_from: vec![NO_PREDECESSOR; cells],
+        };
+        let start = search.index(start);
+        search.costs[start] = 0;
+        search
+            .frontier
+            .push(Reverse((search.heuristic(start), 0, start)));
+        search
     }
-    (came_from, cost_so_far)
-}
 
-/// Reconstruct the lowest-cost path found by the A* search.
-///
-/// Iterates through the `HashMap` of (x, y) -> (x, y) pairs (goal -> start)
-/// then reverses the path so it is returned (start -> goal)
-fn reconstruct_path(came_from: &PathMap, start: Coord, goal: Coord) -> Vec<Coord> {
-    let mut current = goal;
-    let mut path = vec![];
-    while current != start {
-        path.push(current);
-        current = *came_from.get(&current).unwrap();
+    fn index(&self, (x, y): Pos) -> usize {
+        y * self.graph.weights.width() + x
     }
-    path.push(start);
-    path = path.iter().rev().map(|v| *v).collect();
-    path
-}
 
-/// The heuristic for the 2-D `WeightedGrid` (N,E,S,W) is the manhattan distance from the
-/// current (x, y) coordinate to the goal (x, y). Shorter is better (min value)
-fn heuristic(a: Coord, b: Coord) -> f64 {
-    let (x1, y1) = a;
-    let (x2, y2) = b;
-    (x1 - x2).abs() as f64 + (y1 - y2).abs() as f64
-}
+    fn pos(&self, index: usize) -> Pos {
+        let width = self.graph.weights.width();
+        (index % width, index / width)
+    }
 
-/// Graph struct with width, height, and a map of (x, y) coordinates -> weight value
-#[derive(Debug)]
-struct WeightedGraph {
-    width: isize,
-    height: isize,
-    weights: HashMap<Coord, f64>,
-}
+    /// The heuristic for the 2-D `WeightedGraph` (N,E,S,W) is the manhattan distance from the
+    /// current (x, y) coordinate to the goal (x, y), times the least a step can cost, so it never
+    /// overestimates. With risk levels of 0 on the map, that's 0: plain Dijkstra. Shorter is
+    /// better (min value)
+    fn heuristic(&self, index: usize) -> u64 {
+        let (x, y) = self.pos(index);
+        let distance = x.abs_diff(self.goal.0) + y.abs_diff(self.goal.1);
+        (distance as u64 * self.min_step) << RISK_SHIFT
+    }
 
-impl WeightedGraph {
-    /// convenience function to return a value from `self.weights`
-    fn get(&self, id: Coord) -> Option<f64> {
-        match self.weights.get(&id) {
-            Some(result) => Some(*result),
-            None => None,
-        }
+    /// Whether a path to `pos` has been found yet (it may not be the cheapest one until `pos` has
+    /// been yielded).
+    pub fn reached(&self, pos: Pos) -> bool {
+        self.costs[self.index(pos)] != UNREACHED
     }
 
-    /// Get the (x, y) coordinate representing the bottom-right corner of the grid
-    fn bottom_right(&self) -> Coord {
-        (self.width - 1, self.height - 1)
+    /// Total risk of the cheapest known path to `pos`.
+    pub fn risk(&self, pos: Pos) -> Option<u32> {
+        let cost = self.costs[self.index(pos)];
+        (cost != UNREACHED).then_some((cost >> RISK_SHIFT) as u32)
     }
 
-    /// Added the twist to the cost (though mostly unecessary for AoC2021 graph input) from
-    /// Redblob games' implementation [here](https://www.redblobgames.com/pathfinding/a-star/implementation.html#troubleshooting-ugly-path)
-    /// helps make paths "pretty"; even though the cost is the lowest, it may not be the visually/logically "straightest" path to a human.
-    fn cost(&self, from_node: Coord, to_node: Coord) -> f64 {
-        let prev_cost = *self.weights.get(&to_node).unwrap_or(&1.0);
-        let mut nudge = 0.0;
-        let (x1, y1) = from_node;
-        let (x2, y2) = to_node;
-        if ((x1 + y1) % 2 == 0) && x2 != x1 {
-            nudge = 1.0;
-        } else if ((x1 + y1) % 2 == 1) && y2 != y1 {
-            nudge = 1.0;
+    /// Cheapest known path to `pos`, from the start.
+    pub fn path_to(&self, pos: Pos) -> Option<Vec<Pos>> {
+        let mut index = self.index(pos);
+        if self.costs[index] == UNREACHED {
+            return None;
         }
-        prev_cost + 0.001 * nudge
+        let mut path = vec![pos];
+        while self.came_from[index] != NO_PREDECESSOR {
+            index = self.came_from[index];
+            path.push(self.pos(index));
+        }
+        path.reverse();
+        Some(path)
     }
+}
 
-    /// Determine if a coordinate (x, y) is in the bounds of the Grid. Used by `neighbors` function.
-    fn in_bounds(&self, id: Coord) -> bool {
-        let (x, y) = id;
-        0 <= x && x < self.width as isize && 0 <= y && y < self.height as isize
-    }
+impl Iterator for RiskSearch<'_> {
+    type Item = Pos;
 
-    /// Return all E, W, N, S neighbors for a given coordinate (x, y) point.
-    fn neighbors(&self, id: Coord) -> Vec<Coord> {
-        let (x, y) = id;
-        let mut neighbors: Vec<Coord> = Vec::from([(x + 1, y), (x - 1, y), (x, y - 1), (x, y + 1)]);
-        if (x + y) % 2 == 0 {
-            for (i, val) in neighbors.clone().iter().enumerate() {
-                neighbors[i] = *val;
+    fn next(&mut self) -> Option<Pos> {
+        while let Some(Reverse((_, cost, index))) = self.frontier.pop() {
+            // a cheaper way here was found after this entry was queued
+            if cost > self.costs[index] {
+                continue;
+            }
+            let current = self.pos(index);
+            for next in self.graph.weights.neighbors4(current) {
+                let mut step = (self.graph.weights[next] as u64) << RISK_SHIFT;
+                if self.pretty {
+                    step += WeightedGraph::nudge(current, next) as u64;
+                }
+                let new_cost = cost + step;
+                let next = self.index(next);
+                if new_cost < self.costs[next] {
+                    self.costs[next] = new_cost;
+                    self.came_from[next] = index;
+                    self.frontier
+                        .push(Reverse((new_cost + self.heuristic(next), new_cost, next)));
+                }
             }
+            return Some(current);
         }
-        neighbors.retain(|pt| self.in_bounds(*pt));
-        neighbors
+        None
     }
+}
 
-    /// Print a visual representation of the grid with weights.
-    fn display(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                print!("{}", self.get((x, y)).unwrap());
+/// Least risky path from `start` to `goal`, and its total risk. With an `animation`, the search
+/// is drawn as it goes, skipping `frame_skip` settled positions between frames.
+pub fn a_star_search(
+    graph: &WeightedGraph,
+    start: Pos,
+    goal: Pos,
+    pretty: bool,
+    mut animation: Option<&mut Animation>,
+    frame_skip: usize,
+) -> Option<(Vec<Pos>, u32)> {
+    let mut renderer = PathRenderer::new(graph, frame_skip);
+    let mut search = RiskSearch::new(graph, start, goal, pretty);
+    while let Some(current) = search.next() {
+        if let Some(animation) = animation.as_deref_mut() {
+            if renderer.step(&search, current) {
+                animation.draw(&mut renderer);
+            }
+        }
+        if current == goal {
+            let path = search.path_to(goal)?;
+            if let Some(animation) = animation {
+                renderer.show_path(path.clone());
+                animation.finish(&mut renderer);
             }
-            println!();
+            return Some((path, search.risk(goal)?));
         }
     }
+    None
+}
 
-    /// Similar to `display`, except with terminal color codes:
-    /// - Cyan      - A visited node in the least-cost path
-    /// - Dark Grey - A visited node NOT in the least-cost path
-    /// - White     - just a regular old node
-    fn display_with_path(&self, path: &Vec<Coord>, costs: &CostMap) {
-        let visited_nodes = costs.iter().map(|t| *t.0).collect::<Vec<Coord>>();
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let color = if path.contains(&(x, y)) {
-                    "\x1b[1;36m"
-                } else if visited_nodes.contains(&(x, y)) {
-                    "\x1b[1;30m"
-                } else {
-                    ""
-                };
-                print!("{}{}\x1b[0m", color, self.get((x, y)).unwrap());
-            }
-            println!();
+/// Graph of (x, y) coordinates -> weight value
+#[derive(Debug, Clone)]
+pub struct WeightedGraph {
+    weights: Grid<u8>,
+}
+
+impl WeightedGraph {
+    /// Get the (x, y) coordinate representing the bottom-right corner of the grid
+    pub fn bottom_right(&self) -> Pos {
+        (self.weights.width() - 1, self.weights.height() - 1)
+    }
+
+    /// Added the twist to the cost (though mostly unecessary for AoC2021 graph input) from
+    /// Redblob games' implementation [here](https://www.redblobgames.com/pathfinding/a-star/implementation.html#troubleshooting-ugly-path)
+    /// helps make paths "pretty"; even though the cost is the lowest, it may not be the visually/logically "straightest" path to a human.
+    ///
+    /// Only used to break ties between paths of equal risk.
+    fn nudge(from_node: Pos, to_node: Pos) -> u32 {
+        let (x1, y1) = from_node;
+        let (x2, y2) = to_node;
+        if (((x1 + y1) % 2 == 0) && x2 != x1) || (((x1 + y1) % 2 == 1) && y2 != y1) {
+            1
+        } else {
+            0
         }
     }
 
-    /// Expand grid as tiles by factor of `factor`.
-    /// For each expanded tile, weights increase by one unless they are > 9.0, which resets weight to 1.0.
+    /// Tile the grid `across` times horizontally and `down` times vertically, returning the
+    /// bigger graph. Each tile's weights are one higher than the tile to its left (or above it),
+    /// wrapping around according to `wrap`.
     ///
     /// Grid tile
-    /// ```
+    /// ```text
     /// 1 4 8
     /// 1 1 1
     /// 3 9 1
     /// ```
-    /// `expand`ed by a factor of 2 Becomes
-    /// ```
+    /// `tiled(2, 2, Wrap::default())` Becomes
+    /// ```text
     /// 1 4 8 | 2 5 9
     /// 1 1 1 | 2 2 2
     /// 3 9 1 | 4 1 2
@@ -212,111 +219,179 @@ impl WeightedGraph {
     /// 2 2 2 | 3 3 3
     /// 4 1 2 | 5 2 3
     /// ```
-    fn expand(&mut self, factor: isize) {
-        // Expand horizontally first
-        for y in 0..self.height {
-            for f in 0..=factor {
-                for x in 0..self.width {
-                    let new_x = (f + 1) * self.width + x;
-                    let old_x = f * self.width + x;
-                    let cur_val = self.get((old_x, y)).unwrap();
-                    let new_val = cur_val + 1.0;
-
-                    if new_val > 9.0 {
-                        self.weights.insert((new_x, y), 1.0);
-                    } else {
-                        self.weights.insert((new_x, y), new_val);
-                    }
-                }
-            }
+    pub fn tiled(&self, across: usize, down: usize, wrap: Wrap) -> WeightedGraph {
+        WeightedGraph {
+            weights: self
+                .weights
+                .tile(across, down, |&weight, (tile_x, tile_y)| {
+                    wrap.increase(weight, tile_x + tile_y)
+                }),
         }
-        self.width *= factor;
-
-        // Expand vertically
-        for f in 0..=factor {
-            for y in 0..self.height {
-                for x in 0..self.width {
-                    let new_y = (f + 1) * self.height + y;
-                    let old_y = f * self.height + y;
-                    let cur_val = self.get((x, old_y)).unwrap();
-                    let new_val = cur_val + 1.0;
-
-                    if new_val > 9.0 {
-                        self.weights.insert((x, new_y), 1.0);
-                    } else {
-                        self.weights.insert((x, new_y), new_val);
-                    }
-                }
-            }
-        }
-        self.height *= factor;
     }
 }
 
-/// Get the coordinate for the lower right-hand corner of the grid
-fn get_max_xy(pts: &Vec<Coord>) -> Coord {
-    let max_x = pts.iter().map(|pt| pt.0).max().unwrap();
-    let max_y = pts.iter().map(|pt| pt.1).max().unwrap();
-    (max_x, max_y)
+/// How weights wrap around when tiling increases them past `max`: back to `min`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wrap {
+    min: u8,
+    max: u8,
 }
 
-/// Parse input file into a `WeightedGraph` struct
-fn parse_input(path: &str) -> WeightedGraph {
-    // let mut graph = HashMap::new();
-    let mut pt_vec: Vec<((isize, isize), f64)> = vec![];
-    let file = read_to_string(path).unwrap_or_else(|e| panic!("error opening file: {}", e));
-    let mut y = 0;
-    let mut x = 0;
-    for line in file.lines() {
-        line.chars().for_each(|c| {
-            pt_vec.push(((x, y), c.to_digit(10).unwrap() as f64));
-            x += 1
-        });
-        y += 1;
-        x = 0;
+/// The puzzle's rule: risk levels above 9 wrap back around to 1.
+impl Default for Wrap {
+    fn default() -> Self {
+        Wrap { min: 1, max: 9 }
     }
-    let (width, height) = get_max_xy(&pt_vec.iter().map(|pt| pt.0).collect::<Vec<Coord>>());
-    WeightedGraph {
-        width: width + 1,
-        height: height + 1,
-        weights: HashMap::from_iter(pt_vec),
+}
+
+impl Wrap {
+    /// Wrap weights within `min..=max`.
+    ///
+    /// # Panics
+    ///
+    /// If `min > max`, as there's nothing to wrap to.
+    pub fn new(min: u8, max: u8) -> Self {
+        assert!(min <= max, "empty wrap range {}..={}", min, max);
+        Wrap { min, max }
+    }
+
+    /// `weight` increased `by` steps, wrapping within `min..=max`.
+    fn increase(self, weight: u8, by: usize) -> u8 {
+        let span = (self.max - self.min) as usize + 1;
+        let steps = weight.saturating_sub(self.min) as usize + by;
+        self.min + (steps % span) as u8
     }
 }
 
-/// Unused function to determine cost of all nodes visited, but kept the code around...
-// fn get_total_cost(path: &Vec<Coord>, costs: &CostMap) -> f64 {
-//     let mut sum = 0.0;
-//     path.iter().for_each(|pt| sum += costs.get(pt).unwrap());
-//     sum
-// }
-
-pub fn run(example: bool) {
-    let path = if example {
-        "inputs/day15_example.txt"
-    } else {
-        "inputs/day15.txt"
-    };
-    let mut graph = parse_input(path);
+/// Parse puzzle input into a `WeightedGraph` struct
+pub fn parse_input(file: &str) -> Result<WeightedGraph> {
+    Ok(WeightedGraph {
+        weights: Grid::parse_digits(15, file)?,
+    })
+}
 
-    // Part 1
-    let goal = graph.bottom_right();
-    let (came_from, costs) = a_star_search(&graph, (0, 0), goal, *DEBUG);
-    let reconstructed = reconstruct_path(&came_from, (0, 0), goal);
-    if *DEBUG {
-        clear_screen();
-        graph.display_with_path(&reconstructed, &costs);
+/// The plain risk grid, for the generic searches in `crate::search`.
+impl Graph for WeightedGraph {
+    type Node = Pos;
+    type Cost = u32;
+
+    fn neighbors(&self, node: Pos) -> Vec<(Pos, u32)> {
+        self.weights
+            .neighbors4(node)
+            .map(|next| (next, self.weights[next] as u32))
+            .collect()
     }
-    let final_cost = costs.get(&graph.bottom_right()).unwrap().round();
-    println!("Part 1 - Final cost: {:?}", final_cost);
+}
 
-    // Part 2
-    graph.expand(3);
+/// Total risk of the least-risky path from the top left to the bottom right corner.
+///
+/// Animating also makes the path "pretty", as that's the only time anybody sees it.
+fn lowest_risk(graph: &WeightedGraph, config: &RunConfig) -> Result<i64> {
     let goal = graph.bottom_right();
-    let (_, costs) = a_star_search(&graph, (0, 0), goal, *DEBUG);
-    // YIKES... The `.display_with_path` function was toooo heavy for a 500 x 500 grid...
-    // would need to implement a more efficient lookup for visited nodes, etc
-    // let reconstructed = reconstruct_path(&came_from, (0, 0), goal);
-    // graph.display_with_path(&reconstructed, &costs);
-    let final_cost = costs.get(&graph.bottom_right()).unwrap();
-    println!("Part 2 - Final cost {:?}", final_cost);
+    let mut animation = Animation::new(config.playback(FPS));
+    let animation = config.animate.then_some(&mut animation);
+    // by default, draw the search in roughly `ANIMATION_FRAMES` frames, whatever the size of the map
+    let cells = graph.weights.width() * graph.weights.height();
+    let frame_skip = config.frame_skip.unwrap_or(cells / ANIMATION_FRAMES);
+    let (_, risk) = a_star_search(graph, (0, 0), goal, config.animate, animation, frame_skip)
+        .ok_or_else(|| AocError::solve(15, "no path to the bottom right corner"))?;
+    Ok(risk.into())
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = WeightedGraph;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(graph: &Self::Input, config: &RunConfig) -> Result<Answer> {
+        Ok(lowest_risk(graph, config)?.into())
+    }
+
+    fn part2(graph: &Self::Input, config: &RunConfig) -> Result<Answer> {
+        let graph = graph.tiled(5, 5, Wrap::default());
+        Ok(lowest_risk(&graph, config)?.into())
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE_INPUT: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+#[test]
+fn test_tiled_example() {
+    let graph = parse_input(EXAMPLE_INPUT).unwrap();
+    let tiled = graph.tiled(5, 5, Wrap::default());
+    let rows: Vec<String> = tiled
+        .weights
+        .to_string()
+        .lines()
+        .map(String::from)
+        .collect();
+    assert_eq!(rows.len(), 50);
+    assert_eq!(
+        rows[0],
+        "11637517422274862853338597396444961841755517295286"
+    );
+    assert_eq!(
+        rows[49],
+        "67554889357866599146897761125791887223681299833479"
+    );
+    let config = RunConfig::default();
+    assert_eq!(lowest_risk(&graph, &config).unwrap(), 40);
+    assert_eq!(lowest_risk(&tiled, &config).unwrap(), 315);
+    let animated = RunConfig {
+        animate: true,
+        headless: true,
+        ..config
+    };
+    assert_eq!(lowest_risk(&tiled, &animated).unwrap(), 315);
+    // non-square tiling and a different wrap rule
+    let wide = graph.tiled(3, 1, Wrap::new(0, 9));
+    assert_eq!(wide.bottom_right(), (29, 9));
+    assert_eq!(wide.weights[(13, 0)], 4);
+    assert_eq!(wide.weights[(12, 3)], 0);
+    assert_eq!(wide.weights[(22, 3)], 1);
+}
+
+#[test]
+fn test_search_modes() {
+    let example = parse_input(EXAMPLE_INPUT).unwrap();
+    // zero-risk positions (which wrapping to 0 creates) are free to step on: only Dijkstra is safe
+    let zeros = parse_input("000\n061\n001\n").unwrap();
+    for graph in [
+        example.tiled(5, 5, Wrap::default()),
+        example.tiled(5, 5, Wrap::new(0, 9)),
+        zeros,
+    ] {
+        let goal = graph.bottom_right();
+        let expected = crate::search::dijkstra(&graph, (0, 0), goal).unwrap();
+        for pretty in [false, true] {
+            let (path, risk) = a_star_search(&graph, (0, 0), goal, pretty, None, 0).unwrap();
+            assert_eq!(risk, expected.cost);
+            assert_eq!((path[0], path[path.len() - 1]), ((0, 0), goal));
+            let path_risk: u32 = path[1..].iter().map(|&pos| graph.weights[pos] as u32).sum();
+            assert_eq!(path_risk, risk);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "empty wrap range")]
+fn test_empty_wrap() {
+    Wrap::new(5, 2);
 }
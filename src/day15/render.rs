@@ -0,0 +1,174 @@
+//! Animation frames of a `RiskSearch`: the risk map, with the positions the search has reached
+//! greyed out and the path to the position it just settled highlighted.
+//!
+//! Only cells whose color changed since the last frame are redrawn, so frames stay cheap on the
+//! 500x500 part 2 map.
+use std::fmt::Write as _;
+use std::iter;
+use std::mem;
+
+use super::{RiskSearch, WeightedGraph};
+use crate::animation::Scene;
+use crate::grid::{Grid, Pos};
+
+/// How a cell of the risk map is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shade {
+    /// Not reached yet: white
+    Unvisited,
+    /// Reached, but not on the current path: dark grey
+    Visited,
+    /// On the current path: cyan
+    Path,
+}
+
+impl Shade {
+    fn color(self) -> &'static str {
+        match self {
+            Shade::Unvisited => "",
+            Shade::Visited => "\x1b[1;30m",
+            Shade::Path => "\x1b[1;36m",
+        }
+    }
+}
+
+/// Draws a search's progress, one frame per settled position (minus skipped ones).
+pub struct PathRenderer<'g> {
+    graph: &'g WeightedGraph,
+    /// Number of settled positions skipped between drawn frames; 0 draws every one.
+    frame_skip: usize,
+    steps: usize,
+    /// What each cell should look like...
+    shades: Grid<Shade>,
+    /// ...and what it looks like on screen (`None` until it's drawn).
+    drawn: Grid<Option<Shade>>,
+    /// Cells whose shade changed since the last frame (possibly more than once).
+    changed: Vec<Pos>,
+    path: Vec<Pos>,
+}
+
+impl<'g> PathRenderer<'g> {
+    pub fn new(graph: &'g WeightedGraph, frame_skip: usize) -> Self {
+        PathRenderer {
+            graph,
+            frame_skip,
+            steps: 0,
+            shades: graph.weights.map(|_| Shade::Unvisited),
+            drawn: graph.weights.map(|_| None),
+            changed: vec![],
+            path: vec![],
+        }
+    }
+
+    fn shade(&mut self, pos: Pos, shade: Shade) {
+        if self.shades[pos] != shade {
+            self.shades[pos] = shade;
+            self.changed.push(pos);
+        }
+    }
+
+    /// Record that the search settled `current`, reaching its neighbors. Returns whether this
+    /// step should be drawn, or skipped.
+    pub fn step(&mut self, search: &RiskSearch, current: Pos) -> bool {
+        let weights = &self.graph.weights;
+        let reached: Vec<Pos> = iter::once(current)
+            .chain(weights.neighbors4(current))
+            .filter(|&pos| search.reached(pos))
+            .collect();
+        for pos in reached {
+            if self.shades[pos] == Shade::Unvisited {
+                self.shade(pos, Shade::Visited);
+            }
+        }
+        let skip = !self.steps.is_multiple_of(self.frame_skip + 1);
+        self.steps += 1;
+        if skip {
+            return false;
+        }
+        self.show_path(search.path_to(current).unwrap_or_default());
+        true
+    }
+
+    /// Highlight `path` instead of the previous one.
+    pub fn show_path(&mut self, path: Vec<Pos>) {
+        for pos in mem::take(&mut self.path) {
+            self.shade(pos, Shade::Visited);
+        }
+        for &pos in &path {
+            self.shade(pos, Shade::Path);
+        }
+        self.path = path;
+    }
+}
+
+impl Scene for PathRenderer<'_> {
+    /// Escape codes redrawing every cell that changed since the last frame, leaving the cursor
+    /// below the map.
+    fn render(&mut self, redraw: bool) -> String {
+        let weights = &self.graph.weights;
+        let changed = if redraw {
+            self.changed.clear();
+            self.drawn = weights.map(|_| None);
+            weights.positions().collect()
+        } else {
+            mem::take(&mut self.changed)
+        };
+        let mut frame = String::new();
+        for pos in changed {
+            let shade = self.shades[pos];
+            if self.drawn[pos] == Some(shade) {
+                continue;
+            }
+            self.drawn[pos] = Some(shade);
+            // cursor positions are 1-based rows and columns
+            let (x, y) = pos;
+            let _ = write!(
+                frame,
+                "\x1b[{};{}H{}{}\x1b[0m",
+                y + 1,
+                x + 1,
+                shade.color(),
+                weights[pos]
+            );
+        }
+        let _ = write!(frame, "\x1b[{};1H", weights.height() + 1);
+        frame
+    }
+
+    fn last_frame(&mut self) -> String {
+        let mut frame = String::new();
+        for (y, row) in self.graph.weights.rows().enumerate() {
+            for (x, weight) in row.iter().enumerate() {
+                let _ = write!(frame, "{}{}\x1b[0m", self.shades[(x, y)].color(), weight);
+            }
+            frame.push('\n');
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day15::parse_input;
+
+    #[test]
+    fn test_only_changes_are_redrawn() {
+        let graph = parse_input("123\n456\n789\n").unwrap();
+        let mut renderer = PathRenderer::new(&graph, 0);
+        let first = renderer.render(true);
+        assert_eq!(first.matches('H').count(), 9 + 1);
+        // nothing changed: just park the cursor below the map again
+        assert_eq!(renderer.render(false), "\x1b[4;1H");
+
+        renderer.show_path(vec![(0, 0), (1, 0)]);
+        renderer.show_path(vec![(0, 0), (0, 1)]);
+        let frame = renderer.render(false);
+        assert!(frame.contains("\x1b[1;1H\x1b[1;36m1"));
+        assert!(frame.contains("\x1b[1;2H\x1b[1;30m2"));
+        assert!(frame.contains("\x1b[2;1H\x1b[1;36m4"));
+        assert_eq!(frame.matches('H').count(), 3 + 1);
+        assert_eq!(renderer.render(true).matches('H').count(), 9 + 1);
+        assert_eq!(renderer.last_frame().lines().count(), 3);
+    }
+}
@@ -1,26 +1,42 @@
 /// Stolen! Gasp. I know. Was not having luck with this one.
 /// https://gitlab.com/mbryant/aoc-2021/-/blob/main/src/lib.rs#L1853
-type Ranges = ((isize, isize), (isize, isize));
-
-fn parse_input(input: &str) -> Ranges {
-    let mut ranges = input["target area: ".len()..]
-        .trim_end()
-        .split(", ")
-        .map(|range| {
-            range[2..]
-                .split_once("..")
-                .map(|(l, r)| {
-                    (
-                        l.parse().expect("Ranges should be composed of integers"),
-                        r.parse().expect("Ranges should be composed of integers"),
-                    )
-                })
-                .expect("Ranges should be separate")
-        });
-    (
-        ranges.next().expect("Must be a x range"),
-        ranges.next().expect("Must be a y range"),
-    )
+use crate::error::{AocError, InputLine, Result};
+use crate::solution::{Answer, RunConfig, Solution};
+
+pub type Ranges = ((isize, isize), (isize, isize));
+
+/// Parse `target area: x=20..30, y=-10..-5`.
+pub fn parse_input(input: &str) -> Result<Ranges> {
+    let line = InputLine::new(17, 1, input.trim_end());
+    let (x_range, y_range) = line
+        .text
+        .strip_prefix("target area: ")
+        .and_then(|ranges| ranges.split_once(", "))
+        .ok_or_else(|| line.error_at(1, "expected 'target area: x=A..B, y=C..D'"))?;
+    Ok((
+        parse_range(&line, x_range, "x=")?,
+        parse_range(&line, y_range, "y=")?,
+    ))
+}
+
+/// Parse a single `x=A..B` range of the target area.
+fn parse_range(line: &InputLine, range: &str, prefix: &str) -> Result<(isize, isize)> {
+    let (l, r) = range
+        .strip_prefix(prefix)
+        .and_then(|range| range.split_once(".."))
+        .ok_or_else(|| line.error(range, format!("expected a range like '{}A..B'", prefix)))?;
+    Ok((line.parse(l)?, line.parse(r)?))
+}
+
+/// Both parts assume the target area is somewhere below the launcher.
+fn check_below(top_y: isize) -> Result<()> {
+    if top_y >= 0 {
+        return Err(AocError::solve(
+            17,
+            "the target area must be below the probe's start",
+        ));
+    }
+    Ok(())
 }
 
 /// Probe trajectory is a parabola, so it'll always pass back through 0 with `-initial`
@@ -29,18 +45,19 @@ fn parse_input(input: &str) -> Ranges {
 /// The trajectory will be `y + (y-1) + ... = \sum_1^y y`, or `y * (y+1) / 2`.
 ///
 /// TIL: Rust argument destructuring is really cool
-fn part1(&(_, (by, _)): &Ranges) -> isize {
-    (by * (by + 1)) / 2
+pub fn part1(&(_, (by, ty)): &Ranges) -> Result<isize> {
+    check_below(ty)?;
+    Ok((by * (by + 1)) / 2)
 }
 
-fn part2(&((left_x, right_x), (bottom_y, top_y)): &Ranges) -> usize {
-    assert!(top_y < 0, "Assume targets are lower than us");
+pub fn part2(&((left_x, right_x), (bottom_y, top_y)): &Ranges) -> Result<usize> {
+    check_below(top_y)?;
 
     let min_x = (1..)
         .find(|candidate_x| candidate_x * (candidate_x + 1) >= left_x * 2)
         .expect("Must be a minimum x-velocity");
 
-    (min_x..=right_x)
+    Ok((min_x..=right_x)
         .flat_map(|xv| {
             (bottom_y..=0)
                 .filter(move |&yv| {
@@ -93,20 +110,46 @@ fn part2(&((left_x, right_x), (bottom_y, top_y)): &Ranges) -> usize {
                     }
                 }))
         })
-        .count()
+        .count())
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Ranges;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    /// Highest point reachable while still hitting the target.
+    fn part1(ranges: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part1(ranges)?.into())
+    }
+
+    /// Number of possible initial velocities that hit the target area.
+    fn part2(ranges: &Self::Input, _config: &RunConfig) -> Result<Answer> {
+        Ok(part2(ranges)?.into())
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE_INPUT: &str = "target area: x=20..30, y=-10..-5";
+
+#[cfg(test)]
+const PUZZLE_INPUT: &str = "target area: x=169..206, y=-108..-68";
+
+#[test]
+fn test_example() {
+    let ranges = parse_input(EXAMPLE_INPUT).unwrap();
+    assert_eq!(part1(&ranges).unwrap(), 45);
+    assert_eq!(part2(&ranges).unwrap(), 112);
 }
 
-pub fn run(example: bool) {
-    let input = if example {
-        "target area: x=20..30, y=-10..-5"
-    } else {
-        "target area: x=169..206, y=-108..-68"
-    };
-    let ranges = parse_input(input);
-    // Part 1
-    let pt1_result = part1(&ranges);
-    println!("Part 1 - highest point: {pt1_result}");
-
-    let pt2_result = part2(&ranges);
-    println!("Part 2 - number of possible initial\n velocities that hit target area: {pt2_result}");
+#[test]
+fn test_puzzle_input() {
+    let ranges = parse_input(PUZZLE_INPUT).unwrap();
+    assert_eq!(part1(&ranges).unwrap(), 5778);
+    assert_eq!(part2(&ranges).unwrap(), 2576);
 }
@@ -0,0 +1,195 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
+
+/// Everything that can go wrong while loading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    /// Couldn't read an input file.
+    Input {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// Couldn't write an output file (like day15's heatmap).
+    Output {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// Malformed puzzle input. `line` and `column` are 1-based, `text` is the offending line.
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    /// The input parsed fine, but the puzzle has no answer for it (e.g. no bingo board ever wins).
+    Solve {
+        day: u8,
+        reason: String,
+    },
+    /// No solution is registered for this day.
+    UnknownDay(u8),
+}
+
+impl AocError {
+    pub fn solve<S: Into<String>>(day: u8, reason: S) -> Self {
+        AocError::Solve {
+            day,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "{}", e),
+            AocError::Input { path, source } | AocError::Output { path, source } => {
+                write!(f, "{}: {}", path.display(), source)
+            }
+            AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                reason,
+            } => {
+                writeln!(
+                    f,
+                    "day {:02} input, line {}, column {}: {}",
+                    day, line, column, reason
+                )?;
+                writeln!(f, "    {}", text)?;
+                // point at the offending column
+                write!(f, "    {:>width$}", "^", width = *column)
+            }
+            AocError::Solve { day, reason } => write!(f, "day {:02}: {}", day, reason),
+            AocError::UnknownDay(day) => write!(f, "Day {} is not implemented", day),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            AocError::Input { source, .. } | AocError::Output { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+/// A single line of puzzle input, remembered so parse errors can point at it.
+#[derive(Debug, Clone, Copy)]
+pub struct InputLine<'a> {
+    pub day: u8,
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+    pub fn new(day: u8, number: usize, text: &'a str) -> Self {
+        InputLine { day, number, text }
+    }
+
+    /// 1-based column of `token` within this line.
+    ///
+    /// `token` should be a sub-slice of `self.text` (e.g. from `split` or `trim`); otherwise the
+    /// error points at the start of the line.
+    pub fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() {
+            offset + 1
+        } else {
+            1
+        }
+    }
+
+    /// Parse error pointing at `token` within this line.
+    pub fn error<S: Into<String>>(&self, token: &str, reason: S) -> AocError {
+        self.error_at(self.column_of(token), reason)
+    }
+
+    /// Parse error pointing at a 1-based `column` of this line.
+    pub fn error_at<S: Into<String>>(&self, column: usize, reason: S) -> AocError {
+        AocError::Parse {
+            day: self.day,
+            line: self.number,
+            column,
+            text: self.text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Parse `token` (a sub-slice of this line) with `FromStr`.
+    pub fn parse<T>(&self, token: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        token
+            .parse()
+            .map_err(|e| self.error(token, format!("invalid value '{}': {}", token, e)))
+    }
+
+    /// Parse `c` (the char at 0-based `index`) as a digit in the given radix.
+    pub fn digit(&self, index: usize, c: char, radix: u32) -> Result<u32> {
+        c.to_digit(radix)
+            .ok_or_else(|| self.error_at(index + 1, format!("invalid digit '{}'", c)))
+    }
+}
+
+/// Iterate over the lines of `input`, numbered from 1.
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = InputLine<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(i, text)| InputLine::new(day, i + 1, text))
+}
+
+/// Parse error for input that contains no puzzle at all.
+pub fn empty_input(day: u8) -> AocError {
+    InputLine::new(day, 1, "").error_at(1, "empty input")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_points_at_token() {
+        let line = InputLine::new(5, 3, "0,9 -> 5,x");
+        let (_, end) = line.text.split_once(" -> ").unwrap();
+        let err = line.parse::<i32>(&end[2..]).unwrap_err();
+        match &err {
+            AocError::Parse { line, column, .. } => assert_eq!((*line, *column), (3, 10)),
+            _ => panic!("expected a parse error, got {:?}", err),
+        }
+        assert_eq!(
+            err.to_string().lines().last(),
+            Some(format!("    {:>10}", "^").as_str())
+        );
+        assert_eq!(
+            line.digit(4, 'z', 10)
+                .unwrap_err()
+                .to_string()
+                .lines()
+                .count(),
+            3
+        );
+    }
+}
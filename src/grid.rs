@@ -0,0 +1,217 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{empty_input, lines, Result};
+
+/// `(x, y)` position in a grid, with `(0, 0)` in the top-left corner.
+pub type Pos = (usize, usize);
+
+/// E, S, W, N
+const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+/// Orthogonal plus diagonal neighbors, clockwise from E.
+const SURROUNDING: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// Dense 2D grid, stored row-major.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` x `height` cells, given row by row.
+    ///
+    /// Panics if `cells` doesn't hold exactly `width * height` values.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "grid cells don't match its size"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Grid with each cell set to `f((x, y))`.
+    pub fn from_fn<F: FnMut(Pos) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Position `(dx, dy)` away from `pos`, if it's inside the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Bounds-checked access; `None` outside the grid.
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every position along with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` so an empty grid doesn't make `chunks` panic
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// In-bounds E, S, W and N neighbors of `pos`.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// In-bounds orthogonal and diagonal neighbors of `pos`.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        SURROUNDING.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// Grid made of `across` x `down` copies of this one, where each cell of the copy in the
+    /// `(tile_x, tile_y)`th column and row of tiles is `f(cell, (tile_x, tile_y))`.
+    pub fn tile<F: FnMut(&T, Pos) -> T>(&self, across: usize, down: usize, mut f: F) -> Self {
+        let (width, height) = (self.width, self.height);
+        Grid::from_fn(width * across, height * down, |(x, y)| {
+            f(&self[(x % width, y % height)], (x / width, y / height))
+        })
+    }
+
+    /// Grid of the same size with `f` applied to every cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl Grid<u8> {
+    /// Parse rows of decimal digits (heightmaps, octopus energy levels, risk levels, ...).
+    ///
+    /// Every row must have the same number of digits.
+    pub fn parse_digits(day: u8, input: &str) -> Result<Self> {
+        let mut cells = vec![];
+        let (mut width, mut height) = (0, 0);
+        for line in lines(day, input) {
+            let row_width = line.text.chars().count();
+            if height == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(
+                    line.error_at(1, format!("expected {} digits, found {}", width, row_width))
+                );
+            }
+            for (x, c) in line.text.chars().enumerate() {
+                cells.push(line.digit(x, c, 10)? as u8);
+            }
+            height += 1;
+        }
+        if cells.is_empty() {
+            return Err(empty_input(day));
+        }
+        Ok(Grid::new(width, height, cells))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+/// One line per row, cells printed back to back (e.g. a `Grid<char>` prints as the art it holds).
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::parse_digits(9, "123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+        assert_eq!(grid.map(|d| d * 2).to_string(), "246\n81012\n");
+        let tiled = grid.tile(2, 1, |&d, (tile_x, _)| d + tile_x as u8);
+        assert_eq!(tiled.to_string(), "123234\n456567\n");
+        assert!(Grid::parse_digits(9, "123\n45\n").is_err());
+        assert!(Grid::parse_digits(9, "").is_err());
+    }
+}
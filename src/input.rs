@@ -0,0 +1,82 @@
+use std::env;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::error::{AocError, Result};
+
+/// Environment variable pointing at the directory of puzzle inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory holding the puzzle inputs, named `dayNN.txt` and `dayNN_example.txt` by convention.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputDir(PathBuf);
+
+impl InputDir {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        InputDir(path.into())
+    }
+
+    /// Pick the input directory, in order of preference:
+    /// 1. `explicit` (the `--input-dir` flag)
+    /// 2. the `AOC_INPUT_DIR` environment variable
+    /// 3. the `inputs/` directory of this crate, so the binary works from any working directory
+    pub fn resolve(explicit: Option<&Path>) -> Self {
+        if let Some(dir) = explicit {
+            return InputDir::new(dir);
+        }
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(dir) if !dir.is_empty() => InputDir::new(dir),
+            _ => InputDir::default(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Path of a day's puzzle input (or its example).
+    pub fn file(&self, day: u8, example: bool) -> PathBuf {
+        let suffix = if example { "_example" } else { "" };
+        self.0.join(format!("day{:02}{}.txt", day, suffix))
+    }
+
+    /// Read a day's puzzle input (or its example).
+    pub fn read(&self, day: u8, example: bool) -> Result<String> {
+        let path = self.file(day, example);
+        read_to_string(&path).map_err(|source| AocError::Input { path, source })
+    }
+}
+
+/// Read a whole puzzle input piped in on stdin.
+pub fn read_stdin() -> Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+impl Default for InputDir {
+    fn default() -> Self {
+        InputDir::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_files() {
+        let dir = InputDir::resolve(Some(Path::new("/tmp/aoc")));
+        assert_eq!(dir.file(4, false), PathBuf::from("/tmp/aoc/day04.txt"));
+        assert_eq!(
+            dir.file(14, true),
+            PathBuf::from("/tmp/aoc/day14_example.txt")
+        );
+        assert!(InputDir::default().file(1, false).is_file());
+        assert!(matches!(
+            dir.read(25, false),
+            Err(AocError::Input { path, .. }) if path == Path::new("/tmp/aoc/day25.txt")
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+//! `--format json` output: an array of flat `{day, part, answer, elapsed_ms}` records.
+//!
+//! The records are simple enough that they're written by hand rather than pulling in serde.
+use std::time::Duration;
+
+use aoc2021::solution::{Answer, Solved};
+
+/// Quote and escape `s` as a JSON string.
+pub fn string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Numbers stay numbers; text answers (like day13's letters) become strings.
+fn answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => n.to_string(),
+        Answer::Text(s) => string(s),
+    }
+}
+
+fn millis(elapsed: Duration) -> String {
+    format!("{:.3}", elapsed.as_secs_f64() * 1000.0)
+}
+
+/// One record per solved part. With `time`, records also carry the day's `parse_ms`.
+pub fn records(day: u8, solved: &Solved, time: bool) -> Vec<String> {
+    solved
+        .parts
+        .iter()
+        .map(|result| {
+            let parse = if time {
+                format!(", \"parse_ms\": {}", millis(solved.parse))
+            } else {
+                String::new()
+            };
+            format!(
+                "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"elapsed_ms\": {}{}}}",
+                day,
+                result.part,
+                answer(&result.answer),
+                millis(result.elapsed),
+                parse
+            )
+        })
+        .collect()
+}
+
+/// Record for a day that failed to run.
+pub fn error_record(day: u8, error: &str) -> String {
+    format!(
+        "{{\"day\": {}, \"part\": null, \"answer\": null, \"elapsed_ms\": null, \"error\": {}}}",
+        day,
+        string(error)
+    )
+}
+
+/// Print `records` as a JSON array, one record per line.
+pub fn print(records: &[String]) {
+    if records.is_empty() {
+        println!("[]");
+        return;
+    }
+    println!("[\n  {}\n]", records.join(",\n  "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2021::solution::PartResult;
+
+    #[test]
+    fn test_records() {
+        let solved = Solved {
+            parse: Duration::from_micros(1500),
+            parts: vec![
+                PartResult {
+                    part: 1,
+                    answer: Answer::Int(17),
+                    elapsed: Duration::from_micros(250),
+                },
+                PartResult {
+                    part: 2,
+                    answer: Answer::Text("say \"hi\"\n".to_string()),
+                    elapsed: Duration::from_millis(2),
+                },
+            ],
+        };
+        assert_eq!(
+            records(13, &solved, false),
+            [
+                r#"{"day": 13, "part": 1, "answer": 17, "elapsed_ms": 0.250}"#,
+                r#"{"day": 13, "part": 2, "answer": "say \"hi\"\n", "elapsed_ms": 2.000}"#,
+            ]
+        );
+        assert!(records(13, &solved, true)[0].ends_with(r#""parse_ms": 1.500}"#));
+        assert_eq!(
+            error_record(9, "bad\tinput"),
+            r#"{"day": 9, "part": null, "answer": null, "elapsed_ms": null, "error": "bad\tinput"}"#
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Advent of Code 2021 solutions.
+//!
+//! Every `dayNN` module exposes its input parser and part solvers, plus a unit struct
+//! (`day01::Day01`, ...) implementing [`solution::Solution`]. The [`solution::registry`]
+//! lists all implemented days for generic runners like the `aoc2021` binary.
+pub mod animation;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod search;
+pub mod solution;
@@ -1,178 +1,229 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day15;
-mod day16;
-mod day17;
-
-use day01::run as day01_run;
-use day02::run as day02_run;
-use day03::run as day03_run;
-use day04::run as day04_run;
-use day05::run as day05_run;
-use day06::run as day06_run;
-use day07::run as day07_run;
-use day08::run as day08_run;
-use day09::run as day09_run;
-use day10::run as day10_run;
-use day11::run as day11_run;
-use day12::run as day12_run;
-use day13::run as day13_run;
-use day15::run as day15_run;
-use day16::run as day16_run;
-use day17::run as day17_run;
-
-use std::env;
-use std::error::Error;
-use std::fmt;
-
-#[derive(Debug)]
-struct CustomError {
-    details: String,
-}
-impl CustomError {
-    fn new(msg: &str) -> CustomError {
-        CustomError {
-            details: msg.to_string(),
-        }
+mod cli;
+mod json;
+
+use std::fs::read_to_string;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+use std::{env, process};
+
+use aoc2021::error::{AocError, Result};
+use aoc2021::input::{self, InputDir};
+use aoc2021::solution::{self, Solved};
+use aoc2021::{day14, day15};
+use cli::{Command, Days, Format, RunArgs};
+
+/// Run a single day, printing each part's answer.
+fn run_day(args: &RunArgs, dir: &InputDir, day: u8) -> Result<()> {
+    let runner = solution::find(day).ok_or(AocError::UnknownDay(day))?;
+    let config = args.config();
+    let input = match &args.input {
+        None if args.stdin => input::read_stdin()?,
+        Some(path) => read_to_string(path).map_err(|source| AocError::Input {
+            path: path.clone(),
+            source,
+        })?,
+        None => runner.input(dir, config.example)?,
+    };
+
+    if let Some(file) = &args.heatmap {
+        let graph = day15::parse_input(&input)?;
+        day15::write_heatmap(&graph, args.part.unwrap_or(2), file)?;
+        // on stderr, so it doesn't end up in `--format json` output
+        eprintln!("Heatmap written to {}", file.display());
     }
-}
 
-impl fmt::Display for CustomError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
+    if let Some(steps) = args.steps {
+        let polymer = day14::parse_input(&input)?;
+        println!("\nRunning day 14 for {} steps...", steps);
+        println!(
+            "Most minus least common element: {}",
+            day14::solve(&polymer, steps)?
+        );
+        return Ok(());
     }
-}
 
-impl Error for CustomError {
-    fn description(&self) -> &str {
-        &self.details
+    if args.format == Format::Json {
+        let solved = runner.solve(&input, args.part, &config)?;
+        json::print(&json::records(day, &solved, config.timing));
+        return Ok(());
     }
+
+    println!("\nRunning day {}...", runner.day());
+    let solved = runner.solve(&input, args.part, &config)?;
+    for result in &solved.parts {
+        // multi-line answers (day13's folded paper) start on their own line
+        let answer = result.answer.to_string();
+        let sep = if answer.contains('\n') { "\n" } else { " " };
+        println!("Part {}:{}{}", result.part, sep, answer);
+    }
+    if config.timing {
+        println!("Timing:");
+        for (step, elapsed) in timings(&solved) {
+            println!("  {:<6}  {:>10.2?}", step, elapsed);
+        }
+        println!("  {:<6}  {:>10.2?}", "Total", total(&solved));
+    }
+
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    let example: bool = args.contains(&String::from("example"));
-    let query = args
+/// Time spent on each step of a run: parsing, then every part that was solved.
+fn timings(solved: &Solved) -> Vec<(String, Duration)> {
+    let parts = solved
+        .parts
         .iter()
-        .nth(1)
-        .expect("OH MY GOD IT'S ALL BREAKING APART");
-    println!("\nRunning day {}...", query);
-    let result = match query.as_str() {
-        "1" => {
-            day01_run()?;
-            Ok(())
-        }
-        "2" => {
-            day02_run()?;
-            Ok(())
-        }
-        "3" => {
-            day03_run()?;
-            Ok(())
-        }
-        "4" => {
-            day04_run(example)?;
-            Ok(())
-        }
-        "5" => {
-            let part = args
-                .iter()
-                .nth(2)
-                .expect("Please enter part (1 or 2)")
-                .parse()
-                .unwrap();
-            if args.len() == 4 {
-                day05_run(part, args.iter().nth(3))?;
-            } else {
-                day05_run(part, None)?;
+        .map(|result| (format!("Part {}", result.part), result.elapsed));
+    std::iter::once(("Parse".to_string(), solved.parse))
+        .chain(parts)
+        .collect()
+}
+
+fn total(solved: &Solved) -> Duration {
+    timings(solved)
+        .into_iter()
+        .map(|(_, elapsed)| elapsed)
+        .sum()
+}
+
+/// Run every implemented day in `first..=last`, then print a summary table.
+///
+/// A day that errors (or panics) gets an error row, and the remaining days still run.
+/// With `--time`, each day's parse time gets its own column and the total time is printed last.
+fn run_days(args: &RunArgs, dir: &InputDir, first: u8, last: u8) {
+    let config = args.config();
+    // (day, part, answer, parse time, part time)
+    let mut rows: Vec<(u8, String, String, String, String)> = vec![];
+    let mut total_time = Duration::ZERO;
+    let mut records = vec![];
+
+    // Panics are reported in the table, so keep the default hook from printing them too
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    for runner in solution::registry() {
+        let day = runner.day();
+        if day < first || day > last {
+            continue;
+        }
+        let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+            let input = runner.input(dir, config.example)?;
+            runner.solve(&input, args.part, &config)
+        }));
+        match solved {
+            Ok(Ok(solved)) => {
+                total_time += total(&solved);
+                records.extend(json::records(day, &solved, config.timing));
+                for (i, result) in solved.parts.into_iter().enumerate() {
+                    let answer = result.answer.to_string();
+                    let answer = match answer.lines().count() {
+                        0 | 1 => answer,
+                        n => format!("<{} lines of text>", n),
+                    };
+                    rows.push((
+                        day,
+                        result.part.to_string(),
+                        answer,
+                        if i == 0 {
+                            format!("{:.2?}", solved.parse)
+                        } else {
+                            String::new()
+                        },
+                        format!("{:.2?}", result.elapsed),
+                    ));
+                }
             }
-            Ok(())
-        }
-        "6" => {
-            let part = args
-                .iter()
-                .nth(2)
-                .expect("Please enter part: 0 for test data, 1 or 2 for actual puzzle")
-                .parse()
-                .unwrap();
-            if args.len() == 4 && args.iter().nth(3).unwrap() == "example" {
-                day06_run(part, true);
-            } else {
-                day06_run(part, false);
+            Ok(Err(e)) => {
+                // parse errors quote the offending line below the message; keep just the message
+                let msg = e.to_string();
+                records.push(json::error_record(day, &msg));
+                let msg = msg.lines().next().unwrap_or_default();
+                rows.push((
+                    day,
+                    "-".to_string(),
+                    format!("error: {}", msg),
+                    "-".to_string(),
+                    "-".to_string(),
+                ))
             }
-            Ok(())
-        }
-        "7" => {
-            if args.len() == 3 {
-                day07_run(args.iter().nth(2));
-            } else {
-                day07_run(None);
+            Err(payload) => {
+                let msg = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                records.push(json::error_record(day, &format!("panic: {}", msg)));
+                rows.push((
+                    day,
+                    "-".to_string(),
+                    format!("panic: {}", msg),
+                    "-".to_string(),
+                    "-".to_string(),
+                ));
             }
-            Ok(())
         }
-        "8" => {
-            day08_run(example)?;
-            Ok(())
-        }
-        "9" => {
-            day09_run(example);
-            Ok(())
-        }
-        "10" => {
-            day10_run(example);
-            Ok(())
-        }
-        "11" => {
-            if args.contains(&String::from("animate")) {
-                env::set_var("DEBUG", "true")
-            }
-            day11_run(example);
-            Ok(())
-        }
-        "12" => {
-            if args.contains(&String::from("debug")) {
-                env::set_var("DEBUG", "true");
-            }
-            day12_run(example);
-            Ok(())
-        }
-        "13" => {
-            day13_run(example);
+    }
+    panic::set_hook(default_hook);
+
+    if args.format == Format::Json {
+        json::print(&records);
+        return;
+    }
+
+    let width = rows
+        .iter()
+        .map(|r| r.2.len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    let row = |day: &str, part: &str, answer: &str, parse: &str, elapsed: &str| {
+        let parse = if config.timing {
+            format!("  {:>10}", parse)
+        } else {
+            String::new()
+        };
+        println!(
+            "{:>3}  {:>4}  {:<width$}{}  {:>10}",
+            day,
+            part,
+            answer,
+            parse,
+            elapsed,
+            width = width
+        );
+    };
+    row("Day", "Part", "Answer", "Parse", "Time");
+    let rule = |width: usize| "-".repeat(width);
+    row(&rule(3), &rule(4), &rule(width), &rule(10), &rule(10));
+    for (day, part, answer, parse, elapsed) in rows {
+        row(&day.to_string(), &part, &answer, &parse, &elapsed);
+    }
+    if config.timing {
+        row("", "", "Total", "", &format!("{:.2?}", total_time));
+    }
+}
+
+fn run(args: RunArgs) -> Result<()> {
+    let dir = InputDir::resolve(args.input_dir.as_deref());
+    match args.days {
+        Days::One(day) => run_day(&args, &dir, day),
+        Days::Range(first, last) => {
+            run_days(&args, &dir, first, last);
             Ok(())
         }
-        "15" => {
-            if args.contains(&String::from("debug")) {
-                env::set_var("DEBUG", "true");
+    }
+}
+
+fn main() {
+    match cli::parse_args(env::args().skip(1)) {
+        Ok(Command::Run(args)) => {
+            if let Err(e) = run(args) {
+                eprintln!("error: {}", e);
+                process::exit(1);
             }
-            day15_run(example);
-            Ok(())
-        }
-        "16" => {
-            day16_run();
-            Ok(())
-        }
-        "17" => {
-            day17_run(example);
-            Ok(())
         }
-        _ => {
-            // eprintln!("Please enter integer between 1-25");
-            return Err(Box::new(CustomError::new(
-                "Please enter integer between 1-25",
-            )));
+        Ok(Command::Help) => println!("{}", cli::USAGE),
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            process::exit(2);
         }
-    };
-
-    result
+    }
 }
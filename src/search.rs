@@ -0,0 +1,244 @@
+//! Shortest paths over any graph: Dijkstra, A* and breadth-first search.
+//!
+//! Implement [`Graph`] for a puzzle's map, then call [`dijkstra`], [`a_star`] or [`bfs`]. For
+//! visualizations, [`Search`] runs A*/Dijkstra one node at a time and exposes its progress.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A graph with weighted, directed edges.
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+    /// Edge costs must be non-negative; `Default` is a cost of zero.
+    type Cost: Copy + Ord + Default + Add<Output = Self::Cost>;
+
+    /// Nodes reachable from `node` in one step, with the cost of that step.
+    fn neighbors(&self, node: Self::Node) -> Vec<(Self::Node, Self::Cost)>;
+}
+
+/// A path found by one of the searches, from start to goal (both included).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    /// Sum of the costs of every step along the path.
+    pub cost: C,
+}
+
+/// Frontier entry, ordered so that `BinaryHeap` pops the lowest priority first.
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// An A* search in progress. Iterating it settles one node at a time, cheapest (cost so far plus
+/// heuristic) first, and yields that node.
+///
+/// The heuristic must never overestimate the remaining cost to the goal (and should be
+/// consistent), otherwise the paths found aren't the cheapest ones. A heuristic that is always
+/// zero makes this Dijkstra's algorithm.
+pub struct Search<'g, G: Graph, H> {
+    graph: &'g G,
+    start: G::Node,
+    heuristic: H,
+    frontier: BinaryHeap<Entry<G::Node, G::Cost>>,
+    came_from: HashMap<G::Node, G::Node>,
+    costs: HashMap<G::Node, G::Cost>,
+}
+
+impl<'g, G, H> Search<'g, G, H>
+where
+    G: Graph,
+    H: Fn(G::Node) -> G::Cost,
+{
+    pub fn new(graph: &'g G, start: G::Node, heuristic: H) -> Self {
+        let mut frontier = BinaryHeap::new();
+        frontier.push(Entry {
+            priority: heuristic(start),
+            cost: G::Cost::default(),
+            node: start,
+        });
+        Search {
+            graph,
+            start,
+            heuristic,
+            frontier,
+            came_from: HashMap::new(),
+            costs: HashMap::from([(start, G::Cost::default())]),
+        }
+    }
+
+    /// Cheapest known cost from the start to every node reached so far.
+    pub fn costs(&self) -> &HashMap<G::Node, G::Cost> {
+        &self.costs
+    }
+
+    /// Cheapest known cost from the start to `node`, if it has been reached.
+    pub fn cost(&self, node: G::Node) -> Option<G::Cost> {
+        self.costs.get(&node).copied()
+    }
+
+    /// Cheapest known path from the start to `node`, if it has been reached. The path is final
+    /// once `node` has been yielded by the search.
+    pub fn path_to(&self, node: G::Node) -> Option<Path<G::Node, G::Cost>> {
+        let cost = self.cost(node)?;
+        let mut nodes = vec![node];
+        let mut current = node;
+        while current != self.start {
+            current = self.came_from[&current];
+            nodes.push(current);
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+impl<G, H> Iterator for Search<'_, G, H>
+where
+    G: Graph,
+    H: Fn(G::Node) -> G::Cost,
+{
+    type Item = G::Node;
+
+    fn next(&mut self) -> Option<G::Node> {
+        while let Some(Entry { cost, node, .. }) = self.frontier.pop() {
+            // a cheaper way to `node` was found after this entry was queued
+            if cost > self.costs[&node] {
+                continue;
+            }
+            for (next, step) in self.graph.neighbors(node) {
+                let new_cost = cost + step;
+                if self.cost(next).is_none_or(|old| new_cost < old) {
+                    self.costs.insert(next, new_cost);
+                    self.came_from.insert(next, node);
+                    self.frontier.push(Entry {
+                        priority: new_cost + (self.heuristic)(next),
+                        cost: new_cost,
+                        node: next,
+                    });
+                }
+            }
+            return Some(node);
+        }
+        None
+    }
+}
+
+/// Cheapest path from `start` to `goal`, guided by `heuristic` (see [`Search`]).
+pub fn a_star<G, H>(
+    graph: &G,
+    start: G::Node,
+    goal: G::Node,
+    heuristic: H,
+) -> Option<Path<G::Node, G::Cost>>
+where
+    G: Graph,
+    H: Fn(G::Node) -> G::Cost,
+{
+    let mut search = Search::new(graph, start, heuristic);
+    search.find(|&node| node == goal)?;
+    search.path_to(goal)
+}
+
+/// Cheapest path from `start` to `goal`.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    goal: G::Node,
+) -> Option<Path<G::Node, G::Cost>> {
+    a_star(graph, start, goal, |_| G::Cost::default())
+}
+
+/// The node (and the cost of the step) a node was first reached from; `None` for the start.
+type ReachedFrom<G> = Option<(<G as Graph>::Node, <G as Graph>::Cost)>;
+
+/// Path from `start` to `goal` with the fewest steps, whatever they cost.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node, goal: G::Node) -> Option<Path<G::Node, G::Cost>> {
+    let mut came_from: HashMap<G::Node, ReachedFrom<G>> = HashMap::new();
+    came_from.insert(start, None);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if node == goal {
+            let mut nodes = vec![goal];
+            let mut cost = G::Cost::default();
+            while let Some((prev, step)) = came_from[nodes.last().unwrap()] {
+                nodes.push(prev);
+                cost = cost + step;
+            }
+            nodes.reverse();
+            return Some(Path { nodes, cost });
+        }
+        for (next, step) in graph.neighbors(node) {
+            came_from.entry(next).or_insert_with(|| {
+                queue.push_back(next);
+                Some((node, step))
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighted edges between numbered nodes.
+    struct Edges(Vec<(u8, u8, u32)>);
+
+    impl Graph for Edges {
+        type Node = u8;
+        type Cost = u32;
+
+        fn neighbors(&self, node: u8) -> Vec<(u8, u32)> {
+            self.0
+                .iter()
+                .filter(|(from, _, _)| *from == node)
+                .map(|&(_, to, cost)| (to, cost))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_searches() {
+        // 0 -> 3 directly costs 10, going around through 1 and 2 costs 3
+        let graph = Edges(vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (0, 3, 10), (3, 4, 2)]);
+        let cheapest = Path {
+            nodes: vec![0, 1, 2, 3, 4],
+            cost: 5,
+        };
+        assert_eq!(dijkstra(&graph, 0, 4), Some(cheapest.clone()));
+        // distance to 4 as the crow flies: never more than the real cost
+        assert_eq!(a_star(&graph, 0, 4, |n| (4 - n) as u32), Some(cheapest));
+        assert_eq!(
+            bfs(&graph, 0, 4),
+            Some(Path {
+                nodes: vec![0, 3, 4],
+                cost: 12
+            })
+        );
+        assert_eq!(dijkstra(&graph, 4, 0), None);
+        assert_eq!(bfs(&graph, 4, 0), None);
+        assert_eq!(dijkstra(&graph, 2, 2).map(|p| p.nodes), Some(vec![2]));
+    }
+}
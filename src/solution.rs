@@ -0,0 +1,209 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::animation::Playback;
+use crate::error::Result;
+use crate::input::InputDir;
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17,
+};
+
+/// The answer to one part of a puzzle.
+///
+/// Most days produce a number, a few (like day13's folded letters) produce text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// Answer to a single part, along with the wall time spent solving it.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Everything a single run produced: time spent parsing the input, then each part's result.
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub parse: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+/// How much a day prints while solving, besides its answers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    #[default]
+    Normal,
+    /// Print debugging output (like day12's adjacency matrix).
+    Debug,
+}
+
+/// How a day is run, as opposed to what it's run on. Passed to every part, so runs in the same
+/// process (`run all`, tests running in parallel) can each be configured differently.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    pub verbosity: Verbosity,
+    /// Animate the puzzle in the terminal (day11, day13, day15).
+    pub animate: bool,
+    /// Animation frame rate, instead of the day's own.
+    pub fps: Option<u32>,
+    /// Wait for Enter before each animation frame.
+    pub step: bool,
+    /// Render animation frames without drawing them.
+    pub headless: bool,
+    /// Steps skipped between animation frames, instead of the day's own (day15).
+    pub frame_skip: Option<usize>,
+    /// Run against the example input.
+    pub example: bool,
+    /// Report parse/part durations.
+    pub timing: bool,
+}
+
+impl RunConfig {
+    pub fn debug(&self) -> bool {
+        self.verbosity >= Verbosity::Debug
+    }
+
+    /// How to play an animation that runs at `default_fps` unless told otherwise.
+    pub fn playback(&self, default_fps: f64) -> Playback {
+        Playback {
+            fps: self.fps.map_or(default_fps, f64::from),
+            step: self.step,
+            headless: self.headless,
+        }
+    }
+}
+
+/// Common shape of every day's puzzle: parse the input once, then solve both parts from it.
+pub trait Solution {
+    /// Day of the advent calendar (1-25).
+    const DAY: u8;
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+
+    /// Load the raw puzzle text. Defaults to `dayNN.txt` (or `dayNN_example.txt`) in `dir`.
+    fn input(dir: &InputDir, example: bool) -> Result<String> {
+        dir.read(Self::DAY, example)
+    }
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input, config: &RunConfig) -> Result<Answer>;
+
+    fn part2(input: &Self::Input, config: &RunConfig) -> Result<Answer>;
+}
+
+/// Object-safe view of a `Solution`, so days with different `Input` types can share a registry.
+pub trait Runner {
+    fn day(&self) -> u8;
+
+    /// Load this day's default puzzle input (or its example) from `dir`.
+    fn input(&self, dir: &InputDir, example: bool) -> Result<String>;
+
+    /// Parse `input`, then solve the requested part (or both parts when `part` is `None`).
+    fn solve(&self, input: &str, part: Option<u8>, config: &RunConfig) -> Result<Solved>;
+}
+
+type PartFn<I> = fn(&I, &RunConfig) -> Result<Answer>;
+
+struct Registered<S>(PhantomData<S>);
+
+impl<S: Solution> Runner for Registered<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn input(&self, dir: &InputDir, example: bool) -> Result<String> {
+        S::input(dir, example)
+    }
+
+    fn solve(&self, input: &str, part: Option<u8>, config: &RunConfig) -> Result<Solved> {
+        let start = Instant::now();
+        let input = S::parse(input)?;
+        let parse = start.elapsed();
+        let solvers: [(u8, PartFn<S::Input>); 2] = [(1, S::part1), (2, S::part2)];
+        let mut results = vec![];
+        for (n, solver) in solvers {
+            if part.is_none_or(|p| p == n) {
+                let start = Instant::now();
+                let answer = solver(&input, config)?;
+                results.push(PartResult {
+                    part: n,
+                    answer,
+                    elapsed: start.elapsed(),
+                });
+            }
+        }
+        Ok(Solved {
+            parse,
+            parts: results,
+        })
+    }
+}
+
+fn register<S: Solution + 'static>() -> Box<dyn Runner> {
+    Box::new(Registered::<S>(PhantomData))
+}
+
+/// Every implemented day, in calendar order.
+pub fn registry() -> Vec<Box<dyn Runner>> {
+    vec![
+        register::<day01::Day01>(),
+        register::<day02::Day02>(),
+        register::<day03::Day03>(),
+        register::<day04::Day04>(),
+        register::<day05::Day05>(),
+        register::<day06::Day06>(),
+        register::<day07::Day07>(),
+        register::<day08::Day08>(),
+        register::<day09::Day09>(),
+        register::<day10::Day10>(),
+        register::<day11::Day11>(),
+        register::<day12::Day12>(),
+        register::<day13::Day13>(),
+        register::<day14::Day14>(),
+        register::<day15::Day15>(),
+        register::<day16::Day16>(),
+        register::<day17::Day17>(),
+    ]
+}
+
+/// Look up a single day in the registry.
+pub fn find(day: u8) -> Option<Box<dyn Runner>> {
+    registry().into_iter().find(|r| r.day() == day)
+}
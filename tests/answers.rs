@@ -0,0 +1,91 @@
+//! Run every registered day against its example and puzzle input, and compare the answers with
+//! `inputs/answers.txt`.
+use std::fs::read_to_string;
+
+use aoc2021::input::InputDir;
+use aoc2021::solution::{registry, RunConfig};
+
+/// One line of the answers file.
+struct Expected {
+    day: u8,
+    example: bool,
+    /// `None` for answers that aren't checked (`-`).
+    answers: [Option<String>; 2],
+}
+
+fn expected_answers(dir: &InputDir) -> Vec<Expected> {
+    let path = dir.path().join("answers.txt");
+    let contents = read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let answer = |field: &str| (field != "-").then(|| field.to_string());
+            match fields[..] {
+                [day, input @ ("input" | "example"), part1, part2] => Expected {
+                    day: day.parse().expect("day should be a number"),
+                    example: input == "example",
+                    answers: [answer(part1), answer(part2)],
+                },
+                _ => panic!("answers.txt line {}: malformed line '{}'", i + 1, line),
+            }
+        })
+        .collect()
+}
+
+/// Solve every day with the example (or puzzle) input, reporting all mismatches at once.
+fn check_answers(example: bool) {
+    let dir = InputDir::default();
+    let expected = expected_answers(&dir);
+    let mut failures = vec![];
+    for runner in registry() {
+        let day = runner.day();
+        let Some(expected) = expected
+            .iter()
+            .find(|e| e.day == day && e.example == example)
+        else {
+            failures.push(format!("day {:02}: missing from answers.txt", day));
+            continue;
+        };
+        if expected.answers.iter().all(Option::is_none) {
+            continue;
+        }
+        let config = RunConfig {
+            example,
+            ..Default::default()
+        };
+        let results = runner
+            .input(&dir, example)
+            .and_then(|input| runner.solve(&input, None, &config));
+        let results = match results {
+            Ok(results) => results,
+            Err(e) => {
+                failures.push(format!("day {:02}: {}", day, e));
+                continue;
+            }
+        };
+        for (result, answer) in results.parts.iter().zip(&expected.answers) {
+            if let Some(answer) = answer {
+                if result.answer.to_string() != *answer {
+                    failures.push(format!(
+                        "day {:02} part {}: expected {}, got {}",
+                        day, result.part, answer, result.answer
+                    ));
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_examples() {
+    check_answers(true);
+}
+
+#[test]
+fn test_puzzle_inputs() {
+    check_answers(false);
+}